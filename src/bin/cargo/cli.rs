@@ -471,7 +471,18 @@ impl Exec {
     #[tracing::instrument(skip_all)]
     fn exec(self, gctx: &mut GlobalContext, subcommand_args: &ArgMatches) -> CliResult {
         match self {
-            Self::Builtin(exec) => exec(gctx, subcommand_args),
+            Self::Builtin(exec) => {
+                // A script's `[env]` applies the same as when running it directly
+                if let Some(manifest_path) = subcommand_args.value_of_path("manifest-path", gctx) {
+                    if gctx.cli_unstable().script
+                        && cargo::util::toml::is_embedded(&manifest_path)
+                        && manifest_path.is_file()
+                    {
+                        commands::run::add_script_env_config(gctx, &manifest_path)?;
+                    }
+                }
+                exec(gctx, subcommand_args)
+            }
             Self::Manifest(cmd) => {
                 let ext_path = super::find_external_subcommand(gctx, &cmd);
                 if !gctx.cli_unstable().script && ext_path.is_some() {
@@ -0,0 +1,92 @@
+use crate::command_prelude::*;
+use cargo::core::compiler::MessageFormat;
+use cargo::util::edit_distance::closest_msg;
+use cargo::util::lints::LINTS;
+
+pub fn cli() -> Command {
+    subcommand("manifest-lints")
+        .about("Check manifests for lints")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            subcommand("check")
+                .about("Run the `[lints.cargo]` lints without compiling")
+                .arg(
+                    opt("message-format", "Error format")
+                        .value_name("FMT")
+                        .value_parser(["human", "short", "json"])
+                        .default_value("human"),
+                )
+                .arg(multi_opt(
+                    "lint",
+                    "NAME",
+                    "Only run the given lint, ignoring all others (can be repeated)",
+                ))
+                .arg_silent_suggestion()
+                .arg_manifest_path(),
+        )
+        .subcommand(subcommand("list").about(
+            "List the `[lints.cargo]` lints, their default level, and the version that added them",
+        ))
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+    gctx.cli_unstable().fail_if_stable_command(
+        gctx,
+        "manifest-lints",
+        12235,
+        "cargo-lints",
+        gctx.cli_unstable().cargo_lints,
+    )?;
+    match args.subcommand() {
+        Some(("check", args)) => {
+            let message_format = match args.get_one::<String>("message-format").unwrap().as_str() {
+                "short" => MessageFormat::Short,
+                "json" => MessageFormat::Json {
+                    render_diagnostics: false,
+                    short: false,
+                    ansi: false,
+                },
+                _ => MessageFormat::Human,
+            };
+            let allowlist = args._values_of("lint");
+            for name in &allowlist {
+                if !LINTS.iter().any(|lint| lint.name == name.as_str()) {
+                    let help = closest_msg(name, LINTS.iter(), |lint| lint.name);
+                    return Err(anyhow::format_err!("unknown lint `{name}`{help}").into());
+                }
+            }
+            gctx.set_lint_allowlist(allowlist.into_iter().collect());
+            let ws = args.workspace(gctx)?;
+            let lint_errors = ws.emit_warnings_and_lints(message_format)?;
+            if lint_errors > 0 {
+                let s = if lint_errors == 1 { "" } else { "s" };
+                return Err(anyhow::format_err!(
+                    "could not check lints due to {lint_errors} previous error{s}"
+                )
+                .into());
+            }
+        }
+        Some(("list", _)) => {
+            let lints = LINTS.iter().filter(|lint| lint.docs.is_some());
+            let width = lints.clone().map(|lint| lint.name.len()).max().unwrap_or(0);
+            for lint in lints {
+                let level = lint.default_level.to_string();
+                cargo::drop_println!(
+                    gctx,
+                    "{:width$}  {level:6} {:6} {}",
+                    lint.name,
+                    lint.since,
+                    lint.desc
+                );
+            }
+        }
+        Some((cmd, _)) => {
+            unreachable!("unexpected command {}", cmd)
+        }
+        None => {
+            unreachable!("unexpected command")
+        }
+    }
+    Ok(())
+}
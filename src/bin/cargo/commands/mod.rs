@@ -19,6 +19,7 @@ pub fn builtin() -> Vec<Command> {
         locate_project::cli(),
         login::cli(),
         logout::cli(),
+        manifest_lints::cli(),
         metadata::cli(),
         new::cli(),
         owner::cli(),
@@ -31,6 +32,7 @@ pub fn builtin() -> Vec<Command> {
         run::cli(),
         rustc::cli(),
         rustdoc::cli(),
+        script::cli(),
         search::cli(),
         test::cli(),
         tree::cli(),
@@ -64,6 +66,7 @@ pub fn builtin_exec(cmd: &str) -> Option<Exec> {
         "locate-project" => locate_project::exec,
         "login" => login::exec,
         "logout" => logout::exec,
+        "manifest-lints" => manifest_lints::exec,
         "metadata" => metadata::exec,
         "new" => new::exec,
         "owner" => owner::exec,
@@ -76,6 +79,7 @@ pub fn builtin_exec(cmd: &str) -> Option<Exec> {
         "run" => run::exec,
         "rustc" => rustc::exec,
         "rustdoc" => rustdoc::exec,
+        "script" => script::exec,
         "search" => search::exec,
         "test" => test::exec,
         "tree" => tree::exec,
@@ -107,6 +111,7 @@ pub mod install;
 pub mod locate_project;
 pub mod login;
 pub mod logout;
+pub mod manifest_lints;
 pub mod metadata;
 pub mod new;
 pub mod owner;
@@ -119,6 +124,7 @@ pub mod report;
 pub mod run;
 pub mod rustc;
 pub mod rustdoc;
+pub mod script;
 pub mod search;
 pub mod test;
 pub mod tree;
@@ -1,13 +1,19 @@
+use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::ffi::OsString;
 use std::path::Path;
+use std::path::PathBuf;
 
 use crate::command_prelude::*;
 use crate::util::restricted_names::is_glob_pattern;
+use anyhow::Context as _;
+use cargo::core::compiler::TimingOutput;
 use cargo::core::Verbosity;
 use cargo::core::Workspace;
 use cargo::ops::{self, CompileFilter, Packages};
 use cargo::util::closest;
+use cargo::AlreadyPrintedError;
+use cargo::CargoResult;
 use cargo_util::ProcessError;
 use itertools::Itertools as _;
 
@@ -86,16 +92,29 @@ pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
     ops::run(&ws, &compile_opts, &values_os(args, "args")).map_err(|err| to_run_error(gctx, err))
 }
 
+/// Script path for reading the script from stdin
+const STDIN_SCRIPT: &str = "-";
+
 /// See also `util/toml/mod.rs`s `is_embedded`
 pub fn is_manifest_command(arg: &str) -> bool {
     let path = Path::new(arg);
-    1 < path.components().count()
+    arg == STDIN_SCRIPT
+        || 1 < path.components().count()
         || path.extension() == Some(OsStr::new("rs"))
         || path.file_name() == Some(OsStr::new("Cargo.toml"))
 }
 
 pub fn exec_manifest_command(gctx: &mut GlobalContext, cmd: &str, args: &[OsString]) -> CliResult {
-    let manifest_path = Path::new(cmd);
+    let stdin_path;
+    let manifest_path = if cmd == STDIN_SCRIPT {
+        if !gctx.cli_unstable().script {
+            return Err(anyhow::anyhow!("running a script from stdin requires `-Zscript`").into());
+        }
+        stdin_path = stdin_script(gctx)?;
+        stdin_path.as_path()
+    } else {
+        Path::new(cmd)
+    };
     match (manifest_path.is_file(), gctx.cli_unstable().script) {
         (true, true) => {}
         (true, false) => {
@@ -170,25 +189,150 @@ pub fn exec_manifest_command(gctx: &mut GlobalContext, cmd: &str, args: &[OsStri
         }
     }
 
+    // Like `cargo run -- <args>`, a leading `--` separates the script's arguments and is not one of
+    // them, so `cargo file.rs -- --` passes a literal `--`
+    let args = match args.split_first() {
+        Some((separator, rest)) if separator == "--" => rest,
+        _ => args,
+    };
+
+    // A script from stdin has no location of its own, so it keeps the current environment.
+    exec_script(gctx, manifest_path, cmd != STDIN_SCRIPT, Vec::new(), args)
+}
+
+/// Applies the `[env]` table of the script at `manifest_path` to the config
+///
+/// A script's `[env]` is only for this invocation, so it is applied as config rather than as part
+/// of the manifest. The config is reloaded from the current directory.
+pub fn add_script_env_config(gctx: &mut GlobalContext, manifest_path: &Path) -> CargoResult<()> {
+    let env_config = cargo::util::toml::embedded_env_config(manifest_path)?;
+    if env_config.is_empty() {
+        return Ok(());
+    }
+    gctx.add_default_cli_config(env_config);
+    let cwd = gctx.cwd().to_owned();
+    gctx.reload_rooted_at(cwd)
+}
+
+/// Builds and runs the script at `manifest_path`, passing it `args`
+///
+/// When `rooted`, the config is re-evaluated from the directory the script resides in. Any
+/// `timing_outputs` are written to the script's target directory.
+pub fn exec_script(
+    gctx: &mut GlobalContext,
+    manifest_path: &Path,
+    rooted: bool,
+    timing_outputs: Vec<TimingOutput>,
+    args: &[OsString],
+) -> CliResult {
     let manifest_path = root_manifest(Some(manifest_path), gctx)?;
 
     // Treat `cargo foo.rs` like `cargo install --path foo` and re-evaluate the config based on the
     // location where the script resides, rather than the environment from where it's being run.
-    let parent_path = manifest_path
-        .parent()
-        .expect("a file should always have a parent");
-    gctx.reload_rooted_at(parent_path)?;
+    if rooted {
+        let env_config = cargo::util::toml::embedded_env_config(&manifest_path)?;
+        gctx.add_default_cli_config(env_config);
+        let parent_path = manifest_path
+            .parent()
+            .expect("a file should always have a parent");
+        gctx.reload_rooted_at(parent_path)?;
+    } else {
+        add_script_env_config(gctx, &manifest_path)?;
+    }
+
+    if gctx.cli_unstable().script_print.as_deref() == Some("expanded-source") {
+        let source = cargo::util::toml::embedded_rust_source(&manifest_path)?;
+        cargo::drop_print!(gctx, "{source}");
+        return Ok(());
+    }
+
+    if let Some(out) = gctx.cli_unstable().script_manifest_out.clone() {
+        let manifest = cargo::util::toml::read_toml_string(&manifest_path, gctx)?;
+        if out == "-" {
+            cargo::drop_print!(gctx, "{manifest}");
+        } else {
+            cargo_util::paths::write(&out, manifest)?;
+        }
+        return Ok(());
+    }
 
     let mut ws = Workspace::new(&manifest_path, gctx)?;
     if gctx.cli_unstable().avoid_dev_deps {
         ws.set_require_optional_deps(false);
     }
+    cargo::ops::prune_script_cache(gctx, &manifest_path, &ws.target_dir().into_path_unlocked())?;
 
     let mut compile_opts =
         cargo::ops::CompileOptions::new(gctx, cargo::core::compiler::CompileMode::Build)?;
-    compile_opts.spec = cargo::ops::Packages::Default;
+    // The script may have joined a workspace with other members, so only build the script
+    compile_opts.spec = cargo::ops::Packages::Packages(vec![ws.current()?.name().to_string()]);
+    compile_opts.build_config.timing_outputs = timing_outputs;
+
+    cargo::ops::run(&ws, &compile_opts, args)
+        .map_err(|err| with_undeclared_crates_help(&ws, &manifest_path, err))
+        .map_err(|err| to_run_error(gctx, err))
+}
 
-    cargo::ops::run(&ws, &compile_opts, args).map_err(|err| to_run_error(gctx, err))
+/// Points out crates the script seems to use without declaring them when it fails to compile
+///
+/// The help follows the error, so the error is printed here and returned as already printed.
+/// Other errors, like those from running the script itself, are left as they are.
+fn with_undeclared_crates_help(
+    ws: &Workspace<'_>,
+    manifest_path: &Path,
+    err: anyhow::Error,
+) -> anyhow::Error {
+    let Ok(pkg) = ws.current() else {
+        return err;
+    };
+    // Only a failure to compile the script itself can be from a missing dependency
+    let compile_failed = err.chain().nth(1).is_none()
+        && err
+            .to_string()
+            .starts_with(&format!("could not compile `{}`", pkg.name()));
+    if !compile_failed {
+        return err;
+    }
+    // Dependencies from `script.default-dependencies` are only in the expanded manifest
+    let declared: HashSet<_> = pkg
+        .dependencies()
+        .iter()
+        .map(|dep| dep.name_in_toml().replace('-', "_"))
+        .collect();
+    let undeclared = cargo::util::toml::embedded_undeclared_crates(manifest_path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| !declared.contains(name))
+        .map(|name| format!("`{name}`"))
+        .join(", ");
+    if undeclared.is_empty() {
+        return err;
+    }
+
+    let mut shell = ws.gctx().shell();
+    cargo::display_error(&err, &mut shell);
+    drop(shell.note(format!(
+        "if the script uses these crates, add them to its embedded `[dependencies]`: {undeclared}"
+    )));
+    AlreadyPrintedError::new(err).into()
+}
+
+/// Copies a script from stdin into the cargo home so it can be built like any other script
+///
+/// The file is named after a hash of its content, giving the package a stable name and reusing
+/// the same target directory when the same script is piped in again.
+fn stdin_script(gctx: &GlobalContext) -> CargoResult<PathBuf> {
+    let content =
+        std::io::read_to_string(std::io::stdin()).context("failed to read script from stdin")?;
+    let hash = cargo::util::hex::short_hash(&content);
+    let path = cargo::util::toml::stdin_script_dir(gctx)
+        .join(format!("stdin-{hash}.rs"))
+        .into_path_unlocked();
+    if let Some(parent) = path.parent() {
+        cargo_util::paths::create_dir_all(parent)?;
+    }
+    cargo_util::paths::write_if_changed(&path, content)?;
+    Ok(path)
 }
 
 fn suggested_script(cmd: &str) -> Option<String> {
@@ -0,0 +1,114 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::command_prelude::*;
+use cargo::CargoResult;
+
+pub fn cli() -> Command {
+    subcommand("script")
+        .about("Run a Rust expression as a single-file package")
+        .arg(
+            opt("eval", "Expression to run as the body of `fn main`")
+                .value_name("EXPR")
+                .required_unless_present("extract"),
+        )
+        .arg(
+            opt(
+                "extract",
+                "Write the script given as the argument into DIR as a regular package, \
+                 instead of running it",
+            )
+            .value_name("DIR")
+            .value_parser(value_parser!(PathBuf))
+            .conflicts_with("eval"),
+        )
+        .arg(
+            Arg::new("args")
+                .value_name("ARGS")
+                .help("Arguments for the script")
+                .value_parser(value_parser!(OsString))
+                .num_args(0..)
+                .trailing_var_arg(true),
+        )
+        .arg_timings()
+}
+
+pub fn exec(gctx: &mut GlobalContext, args: &ArgMatches) -> CliResult {
+    gctx.cli_unstable().fail_if_stable_command(
+        gctx,
+        "script",
+        12207,
+        "script",
+        gctx.cli_unstable().script,
+    )?;
+    if let Some(dir) = args.get_one::<PathBuf>("extract") {
+        let script = match values_os(args, "args").as_slice() {
+            [script] => PathBuf::from(script),
+            _ => {
+                return Err(anyhow::format_err!(
+                    "`--extract` requires the path of exactly one script"
+                )
+                .into())
+            }
+        };
+        return Ok(extract_script(gctx, &script, dir)?);
+    }
+    let expr = args.get_one::<String>("eval").unwrap();
+    let timing_outputs = args.timing_outputs(gctx)?;
+    let path = eval_script(gctx, expr)?;
+    super::run::exec_script(gctx, &path, false, timing_outputs, &values_os(args, "args"))
+}
+
+/// Package name of every script created from `--eval`
+const EVAL_PACKAGE_NAME: &str = "cargo_eval";
+
+/// Wraps `expr` in a `fn main` and writes it into the cargo home so it can be built like any
+/// other script
+///
+/// The file lives in a directory named after a hash of the expression, reusing the same target
+/// directory when the same expression is evaluated again.
+fn eval_script(gctx: &GlobalContext, expr: &str) -> CargoResult<PathBuf> {
+    let expr = expr.trim().trim_end_matches(';');
+    let content = format!("fn main() {{\n    {expr};\n}}\n");
+    let hash = cargo::util::hex::short_hash(&content);
+    let path = gctx
+        .home()
+        .join("script-eval")
+        .join(hash)
+        .join(format!("{EVAL_PACKAGE_NAME}.rs"))
+        .into_path_unlocked();
+    if let Some(parent) = path.parent() {
+        cargo_util::paths::create_dir_all(parent)?;
+    }
+    cargo_util::paths::write_if_changed(&path, content)?;
+    Ok(path)
+}
+
+/// Writes the script at `path` into `dir` as a regular package, so it can graduate from being a
+/// script
+fn extract_script(gctx: &GlobalContext, path: &Path, dir: &Path) -> CargoResult<()> {
+    let path = gctx.cwd().join(path);
+    let dir = gctx.cwd().join(dir);
+    if dir.join("Cargo.toml").exists() {
+        anyhow::bail!(
+            "destination `{}` already contains a `Cargo.toml`",
+            dir.display()
+        );
+    }
+    let package = cargo::util::toml::embedded_extract_package(&path, gctx)?;
+
+    let src = dir.join("src");
+    cargo_util::paths::create_dir_all(&src)?;
+    cargo_util::paths::write(dir.join("Cargo.toml"), package.manifest)?;
+    cargo_util::paths::write(src.join("main.rs"), package.main)?;
+    if let Some(config) = package.config {
+        let config_dir = dir.join(".cargo");
+        cargo_util::paths::create_dir_all(&config_dir)?;
+        cargo_util::paths::write(config_dir.join("config.toml"), config)?;
+    }
+
+    gctx.shell().status(
+        "Created",
+        format!("package at `{}` from `{}`", dir.display(), path.display()),
+    )
+}
@@ -762,6 +762,7 @@ unstable_cli_options!(
     direct_minimal_versions: bool = ("Resolve minimal dependency versions instead of maximum (direct dependencies only)"),
     doctest_xcompile: bool = ("Compile and run doctests for non-host target using runner config"),
     dual_proc_macros: bool = ("Build proc-macros for both the host and the target"),
+    explain_lint_levels: bool = ("Explain how the level of each emitted cargo lint was determined"),
     features: Option<Vec<String>>,
     gc: bool = ("Track cache usage and \"garbage collect\" unused files"),
     #[serde(deserialize_with = "deserialize_git_features")]
@@ -781,6 +782,8 @@ unstable_cli_options!(
     rustdoc_map: bool = ("Allow passing external documentation mappings to rustdoc"),
     rustdoc_scrape_examples: bool = ("Allows Rustdoc to scrape code examples from reverse-dependencies"),
     script: bool = ("Enable support for single-file, `.rs` packages"),
+    script_manifest_out: Option<String> = ("Write the expanded manifest of a single-file package to a path, or stdout with `-`, instead of running it"),
+    script_print: Option<String> = ("Print the `expanded-source` of a single-file package instead of running it"),
     separate_nightlies: bool,
     skip_rustdoc_fingerprint: bool,
     target_applies_to_host: bool = ("Enable the `target-applies-to-host` key in the .cargo/config.toml file"),
@@ -1256,6 +1259,7 @@ impl CliUnstable {
             "direct-minimal-versions" => self.direct_minimal_versions = parse_empty(k, v)?,
             "doctest-xcompile" => self.doctest_xcompile = parse_empty(k, v)?,
             "dual-proc-macros" => self.dual_proc_macros = parse_empty(k, v)?,
+            "explain-lint-levels" => self.explain_lint_levels = parse_empty(k, v)?,
             "gc" => self.gc = parse_empty(k, v)?,
             "git" => {
                 self.git = v.map_or_else(
@@ -1286,6 +1290,16 @@ impl CliUnstable {
             "separate-nightlies" => self.separate_nightlies = parse_empty(k, v)?,
             "skip-rustdoc-fingerprint" => self.skip_rustdoc_fingerprint = parse_empty(k, v)?,
             "script" => self.script = parse_empty(k, v)?,
+            "script-manifest-out" => {
+                let Some(v) = v else {
+                    bail!("flag -Zscript-manifest-out requires a path, or `-` for stdout");
+                };
+                self.script_manifest_out = Some(v.to_owned())
+            }
+            "script-print" => match v {
+                Some(v @ "expanded-source") => self.script_print = Some(v.to_owned()),
+                _ => bail!("flag -Zscript-print requires `expanded-source`"),
+            },
             "target-applies-to-host" => self.target_applies_to_host = parse_empty(k, v)?,
             "unstable-options" => self.unstable_options = parse_empty(k, v)?,
             _ => bail!("\
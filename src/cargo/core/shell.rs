@@ -395,15 +395,29 @@ impl Shell {
 
     /// Prints the passed in [Message] to stderr
     pub fn print_message(&mut self, message: Message<'_>) -> std::io::Result<()> {
+        let rendered = self.render_message(message);
+        writeln!(self.err(), "{rendered}")
+    }
+
+    /// Renders the passed in [Message] as [`Shell::print_message`] would,
+    /// without printing it
+    pub fn render_message(&mut self, message: Message<'_>) -> String {
         let term_width = self
             .err_width()
             .diagnostic_terminal_width()
             .unwrap_or(annotate_snippets::renderer::DEFAULT_TERM_WIDTH);
-        writeln!(
-            self.err(),
-            "{}",
-            Renderer::styled().term_width(term_width).render(message)
-        )
+        Renderer::styled()
+            .term_width(term_width)
+            .render(message)
+            .to_string()
+    }
+
+    /// Prints messages from [`Shell::render_message`] to stderr as one report
+    pub fn print_report(&mut self, report: &[String]) -> std::io::Result<()> {
+        if report.is_empty() {
+            return Ok(());
+        }
+        writeln!(self.err(), "{}", report.join("\n"))
     }
 }
 
@@ -10,7 +10,7 @@ use itertools::Itertools;
 use tracing::debug;
 use url::Url;
 
-use crate::core::compiler::Unit;
+use crate::core::compiler::{MessageFormat, Unit};
 use crate::core::features::Features;
 use crate::core::registry::PackageRegistry;
 use crate::core::resolver::features::CliFeatures;
@@ -25,7 +25,25 @@ use crate::util::edit_distance;
 use crate::util::errors::{CargoResult, ManifestError};
 use crate::util::interning::InternedString;
 use crate::util::lints::{
-    analyze_cargo_lints_table, check_im_a_teapot, check_implicit_features, unused_dependencies,
+    analyze_cargo_lints_table, check_broad_members_glob,
+    check_cfg_target_has_lib_dependency_mismatch, check_conflicting_dependency_source,
+    check_deep_path_dependency, check_dependency_path_outside_workspace,
+    check_dependency_registry_not_configured, check_deprecated_manifest_key,
+    check_duplicate_feature_value, check_feature_activates_default_of_optional_dep,
+    check_feature_body_references_removed_dep,
+    check_feature_enables_optional_dep_without_question_mark, check_im_a_teapot,
+    check_implicit_features, check_manifest_byte_order_mark,
+    check_manifest_inconsistent_indentation, check_manifest_trailing_whitespace,
+    check_misplaced_lints_table, check_overly_broad_dependency, check_package_field_order,
+    check_panic_setting_in_lib_profile, check_private_dependency_feature,
+    check_redundant_default_features_true, check_redundant_optional_false,
+    check_redundant_target_dependency, check_script_incompatible_key,
+    check_self_referential_feature, check_suspicious_key_casing, check_tab_indentation,
+    check_target_platform_triple_typo, check_unknown_dependency_feature,
+    check_unknown_profile_field, check_unused_feature, check_unused_workspace_package_fields,
+    check_wildcard_dependency, check_workspace_default_members_not_subset,
+    check_workspace_package_unused_in_virtual_only_workspace, check_workspace_resolver_mismatch,
+    unused_dependencies,
 };
 use crate::util::toml::{read_manifest, InheritableFields};
 use crate::util::{
@@ -597,6 +615,36 @@ impl<'gctx> Workspace<'gctx> {
             })
     }
 
+    /// Returns the `[workspace].default-members` entries, as written, that
+    /// don't refer to a member of the workspace
+    ///
+    /// As when loading the workspace, an excluded path that `members` lists is
+    /// allowed.
+    pub fn invalid_default_members(&self) -> CargoResult<Vec<&str>> {
+        let WorkspaceConfig::Root(root_config) = self.root_maybe().workspace_config() else {
+            return Ok(Vec::new());
+        };
+        let Some(default_members) = &root_config.default_members else {
+            return Ok(Vec::new());
+        };
+        let members_paths =
+            root_config.members_paths(root_config.members.as_ref().unwrap_or(&vec![]))?;
+        let mut invalid = Vec::new();
+        for default_member in default_members {
+            for path in root_config.members_paths(std::slice::from_ref(default_member))? {
+                let normalized_path = paths::normalize_path(&path);
+                let is_member = self.members.contains(&normalized_path.join("Cargo.toml"))
+                    || (members_paths.contains(&normalized_path)
+                        && root_config.is_excluded(&normalized_path));
+                if !is_member {
+                    invalid.push(default_member.as_str());
+                    break;
+                }
+            }
+        }
+        Ok(invalid)
+    }
+
     /// Returns an iterator over default packages in this workspace
     pub fn default_members_mut(&mut self) -> impl Iterator<Item = &mut Package> {
         let packages = &mut self.packages.packages;
@@ -764,6 +812,13 @@ impl<'gctx> Workspace<'gctx> {
 
         self.find_path_deps(&root_manifest_path, &root_manifest_path, false)?;
 
+        // A single-file package that joined a workspace (see `script.join-workspace`) cannot be
+        // listed in `workspace.members`, so it is a member of the workspace it resides in
+        let current_manifest = self.current_manifest.clone();
+        if self.packages.load(&current_manifest)?.is_embedded() {
+            self.find_path_deps(&current_manifest, &root_manifest_path, false)?;
+        }
+
         if let Some(default) = default_members_paths {
             for path in default {
                 let normalized_path = paths::normalize_path(&path);
@@ -1152,11 +1207,40 @@ impl<'gctx> Workspace<'gctx> {
     }
 
     pub fn emit_warnings(&self) -> CargoResult<()> {
+        self.emit_warnings_with_format(MessageFormat::Human)
+    }
+
+    /// Like [`Workspace::emit_warnings`], but reports lints using `message_format`
+    pub fn emit_warnings_with_format(&self, message_format: MessageFormat) -> CargoResult<()> {
+        let lint_errors = self.emit_warnings_and_lints(message_format)?;
+        if lint_errors > 0 {
+            Err(crate::util::errors::AlreadyPrintedError::new(anyhow!(
+                "encountered {lint_errors} errors(s) while running lints"
+            ))
+            .into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Like [`Workspace::emit_warnings_with_format`], but returns the number of
+    /// lints that fired at an error level across every package and the
+    /// workspace, rather than failing
+    pub fn emit_warnings_and_lints(&self, message_format: MessageFormat) -> CargoResult<usize> {
+        let mut lint_errors = 0;
+        if self.gctx.cli_unstable().cargo_lints {
+            self.emit_workspace_lints(&mut lint_errors)?;
+        }
         for (path, maybe_pkg) in &self.packages.packages {
             let path = path.join("Cargo.toml");
-            if let MaybePackage::Package(pkg) = maybe_pkg {
-                if self.gctx.cli_unstable().cargo_lints {
-                    self.emit_lints(pkg, &path)?
+            if self.gctx.cli_unstable().cargo_lints {
+                match maybe_pkg {
+                    MaybePackage::Package(pkg) => {
+                        self.emit_lints(pkg, &path, message_format, &mut lint_errors)?
+                    }
+                    MaybePackage::Virtual(vm) => {
+                        self.emit_virtual_lints(vm, &path, &mut lint_errors)?
+                    }
                 }
             }
             let warnings = match maybe_pkg {
@@ -1181,11 +1265,18 @@ impl<'gctx> Workspace<'gctx> {
                 }
             }
         }
-        Ok(())
+        Ok(lint_errors)
     }
 
-    pub fn emit_lints(&self, pkg: &Package, path: &Path) -> CargoResult<()> {
-        let mut error_count = 0;
+    /// Runs the lints of `pkg`, adding the number that fired at an error level
+    /// to `error_count`
+    pub fn emit_lints(
+        &self,
+        pkg: &Package,
+        path: &Path,
+        message_format: MessageFormat,
+        error_count: &mut usize,
+    ) -> CargoResult<()> {
         let toml_lints = pkg
             .manifest()
             .resolved_toml()
@@ -1208,26 +1299,190 @@ impl<'gctx> Workspace<'gctx> {
             MaybePackage::Virtual(v) => v.document(),
         };
 
-        analyze_cargo_lints_table(
+        // Diagnostics are collected and printed together once analysis is done
+        let mut report = Vec::new();
+        let analyzed = analyze_cargo_lints_table(
             pkg,
             &path,
             &cargo_lints,
             ws_contents,
             ws_document,
             self.root_manifest(),
+            message_format,
+            &mut report,
+            self.gctx,
+        )
+        .and_then(|()| {
+            check_im_a_teapot(
+                pkg,
+                &path,
+                &cargo_lints,
+                &mut report,
+                error_count,
+                self.gctx,
+            )
+        });
+        self.gctx.shell().print_report(&report)?;
+        analyzed?;
+        check_implicit_features(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        unused_dependencies(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_suspicious_key_casing(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_script_incompatible_key(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_feature_activates_default_of_optional_dep(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
             self.gctx,
         )?;
-        check_im_a_teapot(pkg, &path, &cargo_lints, &mut error_count, self.gctx)?;
-        check_implicit_features(pkg, &path, &cargo_lints, &mut error_count, self.gctx)?;
-        unused_dependencies(pkg, &path, &cargo_lints, &mut error_count, self.gctx)?;
-        if error_count > 0 {
-            Err(crate::util::errors::AlreadyPrintedError::new(anyhow!(
-                "encountered {error_count} errors(s) while running lints"
-            ))
-            .into())
-        } else {
-            Ok(())
-        }
+        check_feature_body_references_removed_dep(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            self.gctx,
+        )?;
+        check_feature_enables_optional_dep_without_question_mark(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            self.gctx,
+        )?;
+        check_manifest_byte_order_mark(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_manifest_inconsistent_indentation(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_manifest_trailing_whitespace(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_cfg_target_has_lib_dependency_mismatch(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            self.gctx,
+        )?;
+        check_tab_indentation(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_redundant_default_features_true(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_redundant_optional_false(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_unused_feature(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_self_referential_feature(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_duplicate_feature_value(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        check_conflicting_dependency_source(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_wildcard_dependency(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_overly_broad_dependency(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_package_field_order(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_deep_path_dependency(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_dependency_path_outside_workspace(
+            self,
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            self.gctx,
+        )?;
+        check_unknown_profile_field(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_panic_setting_in_lib_profile(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_target_platform_triple_typo(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_redundant_target_dependency(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_private_dependency_feature(pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_unknown_dependency_feature(self, pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_workspace_resolver_mismatch(self, pkg, &path, &cargo_lints, error_count, self.gctx)?;
+        check_deprecated_manifest_key(
+            pkg,
+            &path,
+            &cargo_lints,
+            error_count,
+            message_format,
+            self.gctx,
+        )?;
+        Ok(())
+    }
+
+    /// Runs the lints of the virtual manifest `vm`, adding the number that
+    /// fired at an error level to `error_count`
+    pub fn emit_virtual_lints(
+        &self,
+        vm: &VirtualManifest,
+        path: &Path,
+        error_count: &mut usize,
+    ) -> CargoResult<()> {
+        let cargo_lints = vm
+            .resolved_toml()
+            .workspace
+            .as_ref()
+            .and_then(|ws| ws.lints.as_ref())
+            .and_then(|lints| lints.get("cargo"))
+            .cloned()
+            .unwrap_or(manifest::TomlToolLints::default());
+
+        check_misplaced_lints_table(vm, &path, &cargo_lints, error_count, self.gctx)?;
+        Ok(())
+    }
+
+    /// Emits lints that check the workspace as a whole, such as ones that
+    /// need to look at every member, adding the number that fired at an error
+    /// level to `error_count`
+    pub fn emit_workspace_lints(&self, error_count: &mut usize) -> CargoResult<()> {
+        check_unused_workspace_package_fields(self, error_count, self.gctx)?;
+        check_workspace_package_unused_in_virtual_only_workspace(self, error_count, self.gctx)?;
+        check_workspace_default_members_not_subset(self, error_count, self.gctx)?;
+        check_broad_members_glob(self, error_count, self.gctx)?;
+        check_dependency_registry_not_configured(self, error_count, self.gctx)?;
+        Ok(())
     }
 
     pub fn set_target_dir(&mut self, target_dir: Filesystem) {
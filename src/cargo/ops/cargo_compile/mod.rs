@@ -137,7 +137,7 @@ pub fn compile_with_exec<'a>(
     options: &CompileOptions,
     exec: &Arc<dyn Executor>,
 ) -> CargoResult<Compilation<'a>> {
-    ws.emit_warnings()?;
+    ws.emit_warnings_with_format(options.build_config.message_format)?;
     compile_ws(ws, options, exec)
 }
 
@@ -419,6 +419,10 @@ pub fn create_bcx<'a, 'gctx>(
         interner,
     )?;
 
+    if ws.root_maybe().is_embedded() {
+        warn_on_newer_dep_editions(&units, &unit_graph, gctx)?;
+    }
+
     // TODO: In theory, Cargo should also dedupe the roots, but I'm uncertain
     // what heuristics to use in that case.
     if matches!(build_config.mode, CompileMode::Doc { deps: true, .. }) {
@@ -540,6 +544,40 @@ where `<compatible-ver>` is the latest version supporting rustc {rustc_version}"
     Ok(bcx)
 }
 
+/// Suggest setting the edition of a single-file package when it is defaulted and a direct
+/// dependency uses a newer one.
+///
+/// Scripts commonly rely on the edition being defaulted, so this is easy to miss until a later,
+/// less obvious error.
+fn warn_on_newer_dep_editions(
+    roots: &[Unit],
+    unit_graph: &UnitGraph,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let mut warned = HashSet::new();
+    for root in roots {
+        // An explicitly set edition is a deliberate choice
+        if crate::util::toml::embedded_edition_is_set(root.pkg.manifest_path())? {
+            continue;
+        }
+        let edition = root.pkg.manifest().edition();
+        for dep in &unit_graph[root] {
+            let dep_pkg = &dep.unit.pkg;
+            let dep_edition = dep_pkg.manifest().edition();
+            if dep_edition <= edition || !warned.insert(dep_pkg.package_id()) {
+                continue;
+            }
+            gctx.shell().warn(format!(
+                "dependency `{}` uses edition {dep_edition}, which is newer than edition \
+                 {edition} used by `{}`; consider setting `package.edition = \"{dep_edition}\"`",
+                dep_pkg.package_id(),
+                root.pkg.name(),
+            ))?;
+        }
+    }
+    Ok(())
+}
+
 /// This is used to rebuild the unit graph, sharing host dependencies if possible,
 /// and applying other unit adjustments based on the whole graph.
 ///
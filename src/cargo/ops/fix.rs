@@ -62,6 +62,7 @@ use crate::ops::{self, CompileOptions};
 use crate::util::diagnostic_server::{Message, RustfixDiagnosticServer};
 use crate::util::errors::CargoResult;
 use crate::util::interning::InternedString;
+use crate::util::lints;
 use crate::util::GlobalContext;
 use crate::util::{existing_vcs_repo, LockServer, LockServerClient};
 use crate::{drop_eprint, drop_eprintln};
@@ -109,16 +110,19 @@ pub fn fix(
 
     let mut target_data =
         RustcTargetData::new(original_ws, &opts.compile_opts.build_config.requested_kinds)?;
+    let specs = opts.compile_opts.spec.to_package_id_specs(&original_ws)?;
+    let members: Vec<&Package> = original_ws
+        .members()
+        .filter(|m| specs.iter().any(|spec| spec.matches(m.package_id())))
+        .collect();
     if opts.edition {
-        let specs = opts.compile_opts.spec.to_package_id_specs(&original_ws)?;
-        let members: Vec<&Package> = original_ws
-            .members()
-            .filter(|m| specs.iter().any(|spec| spec.matches(m.package_id())))
-            .collect();
         migrate_manifests(original_ws, &members)?;
 
         check_resolver_change(&original_ws, &mut target_data, opts)?;
     }
+    if gctx.cli_unstable().cargo_lints {
+        fix_manifest_lints(original_ws, &members, opts.edition)?;
+    }
     let mut ws = Workspace::new(&root_manifest, gctx)?;
     ws.set_resolve_honors_rust_version(Some(original_ws.resolve_honors_rust_version()));
 
@@ -345,6 +349,63 @@ fn migrate_manifests(ws: &Workspace<'_>, pkgs: &[&Package]) -> CargoResult<()> {
     Ok(())
 }
 
+/// Applies the fixes for `[lints.cargo]` lints to each package's manifest
+///
+/// When `migrating` to the next edition, the lints are evaluated at that
+/// edition, and only the fixes for lints that it changes the level of are
+/// applied
+fn fix_manifest_lints(ws: &Workspace<'_>, pkgs: &[&Package], migrating: bool) -> CargoResult<()> {
+    for pkg in pkgs {
+        // The manifest of a single-file package is embedded in Rust source
+        if pkg.manifest().is_embedded() {
+            continue;
+        }
+        let existing_edition = pkg.manifest().edition();
+        let prepare_for_edition = existing_edition.saturating_next();
+        let edition = if migrating
+            && (prepare_for_edition.is_stable() || ws.gctx().nightly_features_allowed)
+        {
+            prepare_for_edition
+        } else {
+            existing_edition
+        };
+        if migrating && edition == existing_edition {
+            continue;
+        }
+        let cargo_lints = pkg
+            .manifest()
+            .resolved_toml()
+            .lints
+            .as_ref()
+            .and_then(|lints| lints.lints.get("cargo"))
+            .cloned()
+            .unwrap_or_default();
+        // Re-read the manifest as it may have been migrated to a new edition
+        let mut contents = paths::read(pkg.manifest_path())?;
+        let mut fixes = 0;
+        for (lint, fix) in lints::MANIFEST_FIXES {
+            if migrating && !lint.is_edition_lint(edition) {
+                continue;
+            }
+            if let Some((new_contents, count)) = fix(pkg, &contents, &cargo_lints, edition)? {
+                contents = new_contents;
+                fixes += count;
+            }
+        }
+        if fixes == 0 {
+            continue;
+        }
+
+        let file = pkg.manifest_path();
+        let file = file.strip_prefix(ws.root()).unwrap_or(file);
+        let verb = if fixes == 1 { "fix" } else { "fixes" };
+        let msg = format!("{} ({fixes} {verb})", file.display());
+        ws.gctx().shell().status("Fixed", msg)?;
+        paths::write_atomic(pkg.manifest_path(), contents.as_bytes())?;
+    }
+    Ok(())
+}
+
 fn rename_dep_fields_2024(parent: &mut dyn toml_edit::TableLike, dep_kind: &str) -> usize {
     let mut fixes = 0;
     for target in parent
@@ -38,6 +38,7 @@ pub use self::resolve::{
     add_overrides, get_resolved_packages, resolve_with_previous, resolve_ws, resolve_ws_with_opts,
     WorkspaceResolve,
 };
+pub use self::script_cache::prune_script_cache;
 pub use self::vendor::{vendor, VendorOptions};
 
 pub mod cargo_add;
@@ -62,6 +63,7 @@ mod fix;
 pub(crate) mod lockfile;
 pub(crate) mod registry;
 pub(crate) mod resolve;
+mod script_cache;
 pub mod tree;
 mod vendor;
 
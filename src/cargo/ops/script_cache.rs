@@ -0,0 +1,206 @@
+//! Pruning of the target directories that single-file packages are built in.
+//!
+//! Each script gets its own target directory under `$CARGO_HOME/target`,
+//! named after a hash of the script's path (see
+//! `Workspace::default_target_dir`). Without pruning, these are never removed.
+//!
+//! The modification time of a script's target directory is bumped every time
+//! it is run, so it doubles as the time the script was last used.
+//!
+//! `$CARGO_HOME/target` may be shared with other builds, like one configured
+//! with `build.target-dir`, so only directories holding a [`MARKER`] are
+//! considered. Pruning runs at most once every [`PRUNE_INTERVAL`], tracked by
+//! the modification time of [`LAST_PRUNED`].
+//!
+//! Scripts read from stdin are copied into `$CARGO_HOME/script-stdin`, and
+//! are removed along with their target directory.
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context as _;
+use cargo_util::paths;
+use filetime::FileTime;
+use serde::Deserialize;
+
+use crate::core::gc::{parse_human_size, parse_time_span};
+use crate::util::{Filesystem, GlobalContext};
+use crate::CargoResult;
+
+/// Written into the target directory of every script that is run, holding
+/// the path to the script
+const MARKER: &str = ".cargo-script";
+
+/// Touched in `$CARGO_HOME/target` whenever the script target directories are
+/// pruned
+const LAST_PRUNED: &str = ".cargo-script-last-pruned";
+
+/// How long to wait after pruning before pruning again
+const PRUNE_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The `[script.cache]` config table
+///
+/// If neither option is set, nothing is pruned.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+struct ScriptCacheConfig {
+    /// Target directories of scripts that have not been run for this long
+    /// are removed.
+    max_age: Option<String>,
+    /// Once the target directories take up more than this, those of the least
+    /// recently run scripts are removed.
+    max_size: Option<String>,
+}
+
+/// Marks `current`, the target directory of `script` about to be run, as
+/// just used and prunes the other script target directories according to
+/// `[script.cache]`
+///
+/// Pruning is best-effort: directories that can't be read or removed are
+/// warned about and left in place.
+pub fn prune_script_cache(gctx: &GlobalContext, script: &Path, current: &Path) -> CargoResult<()> {
+    let root = gctx.home().join("target").into_path_unlocked();
+    let is_cached = current.starts_with(&root);
+    if is_cached {
+        // Created like `Layout::new` would, so it is still excluded from backups
+        paths::create_dir_all_excluded_from_backups_atomic(current)?;
+        paths::write_if_changed(current.join(MARKER), paths::path2bytes(script)?)?;
+        paths::set_file_time_no_err(current, FileTime::now());
+    }
+
+    let config = gctx
+        .get::<Option<ScriptCacheConfig>>("script.cache")?
+        .unwrap_or_default();
+    let max_age = config
+        .max_age
+        .as_deref()
+        .map(parse_time_span)
+        .transpose()
+        .context("failed to parse `script.cache.max-age`")?;
+    let max_size = config
+        .max_size
+        .as_deref()
+        .map(parse_human_size)
+        .transpose()
+        .context("failed to parse `script.cache.max-size`")?;
+    if max_age.is_none() && max_size.is_none() {
+        return Ok(());
+    }
+
+    let now = SystemTime::now();
+    let last_pruned = root.join(LAST_PRUNED);
+    if let Ok(pruned) = std::fs::metadata(&last_pruned).and_then(|m| m.modified()) {
+        if now
+            .duration_since(pruned)
+            .is_ok_and(|since| since < PRUNE_INTERVAL)
+        {
+            tracing::debug!("script target directories were pruned recently, skipping");
+            return Ok(());
+        }
+    }
+    if let Err(e) = paths::write(&last_pruned, "") {
+        tracing::debug!("failed to record pruning of script target directories: {e:?}");
+    }
+
+    let stdin_root = crate::util::toml::stdin_script_dir(gctx).into_path_unlocked();
+    let mut entries = cache_entries(&root);
+    entries.retain(|(path, _)| path != current);
+    // Most recently used first, so the size cap evicts the least recently used
+    entries.sort_by_key(|(_, used)| std::cmp::Reverse(*used));
+
+    let mut total_size = if is_cached {
+        cargo_util::du(current, &[]).unwrap_or_default()
+    } else {
+        0
+    };
+    for (path, used) in entries {
+        let expired = max_age.is_some_and(|max_age| {
+            now.duration_since(used)
+                .is_ok_and(|unused_for| max_age < unused_for)
+        });
+        let size = match cargo_util::du(&path, &[]) {
+            Ok(size) => size,
+            Err(e) => {
+                gctx.shell().warn(format!(
+                    "failed to read script target directory `{}`: {e}",
+                    path.display()
+                ))?;
+                continue;
+            }
+        };
+        let over_size = max_size.is_some_and(|max_size| max_size < total_size + size);
+        if !expired && !over_size {
+            total_size += size;
+            continue;
+        }
+        if is_locked(&path) {
+            tracing::debug!(
+                "skipping script target directory `{}`, it is being built",
+                path.display()
+            );
+            total_size += size;
+            continue;
+        }
+        tracing::debug!("pruning script target directory `{}`", path.display());
+        let script = paths::read_bytes(&path.join(MARKER))
+            .ok()
+            .and_then(|script| paths::bytes2path(&script).ok());
+        if let Err(e) = paths::remove_dir_all(&path) {
+            gctx.shell().warn(format!(
+                "failed to remove unused script target directory `{}`: {e}",
+                path.display()
+            ))?;
+            continue;
+        }
+        // Only succeeds once the last entry sharing the prefix is gone
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::remove_dir(parent);
+        }
+        if let Some(script) = script.filter(|script| script.starts_with(&stdin_root)) {
+            let _ = std::fs::remove_file(script);
+        }
+    }
+    Ok(())
+}
+
+/// Lists the script target directories under `root` with when they were last
+/// used
+///
+/// Entries that can't be read are skipped.
+fn cache_entries(root: &Path) -> Vec<(PathBuf, SystemTime)> {
+    let read_dir = |path: &Path| {
+        std::fs::read_dir(path)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+    };
+    read_dir(root)
+        .flat_map(|prefix| read_dir(&prefix).collect::<Vec<_>>())
+        .filter(|path| path.join(MARKER).is_file())
+        .filter_map(|path| {
+            let used = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, used))
+        })
+        .collect()
+}
+
+/// Whether a build in the target directory at `path` holds its lock
+fn is_locked(path: &Path) -> bool {
+    // `<profile>/.cargo-lock`, or `<triple>/<profile>/.cargo-lock` when cross-compiling
+    walkdir::WalkDir::new(path)
+        .min_depth(2)
+        .max_depth(3)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name() == ".cargo-lock")
+        .any(|entry| {
+            let lock = entry.path();
+            let parent = Filesystem::new(lock.parent().unwrap().to_owned());
+            !matches!(
+                parent.try_open_rw_exclusive_create(lock.file_name().unwrap()),
+                Ok(Some(_))
+            )
+        })
+}
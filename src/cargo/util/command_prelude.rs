@@ -625,6 +625,37 @@ Run `{cmd}` to see possible targets."
         )
     }
 
+    /// The report formats requested with `--timings`, defaulting to HTML
+    /// when no format is given
+    fn timing_outputs(&self, gctx: &GlobalContext) -> CargoResult<Vec<TimingOutput>> {
+        let mut timing_outputs = Vec::new();
+        if self._contains("timings") {
+            for timing_output in self._values_of("timings") {
+                for timing_output in timing_output.split(',') {
+                    let timing_output = timing_output.to_ascii_lowercase();
+                    let timing_output = match timing_output.as_str() {
+                        "html" => {
+                            gctx.cli_unstable()
+                                .fail_if_stable_opt("--timings=html", 7405)?;
+                            TimingOutput::Html
+                        }
+                        "json" => {
+                            gctx.cli_unstable()
+                                .fail_if_stable_opt("--timings=json", 7405)?;
+                            TimingOutput::Json
+                        }
+                        s => bail!("invalid timings output specifier: `{}`", s),
+                    };
+                    timing_outputs.push(timing_output);
+                }
+            }
+            if timing_outputs.is_empty() {
+                timing_outputs.push(TimingOutput::Html);
+            }
+        }
+        Ok(timing_outputs)
+    }
+
     fn compile_options(
         &self,
         gctx: &GlobalContext,
@@ -709,30 +740,7 @@ Run `{cmd}` to see possible targets."
         build_config.unit_graph = self.flag("unit-graph");
         build_config.future_incompat_report = self.flag("future-incompat-report");
 
-        if self._contains("timings") {
-            for timing_output in self._values_of("timings") {
-                for timing_output in timing_output.split(',') {
-                    let timing_output = timing_output.to_ascii_lowercase();
-                    let timing_output = match timing_output.as_str() {
-                        "html" => {
-                            gctx.cli_unstable()
-                                .fail_if_stable_opt("--timings=html", 7405)?;
-                            TimingOutput::Html
-                        }
-                        "json" => {
-                            gctx.cli_unstable()
-                                .fail_if_stable_opt("--timings=json", 7405)?;
-                            TimingOutput::Json
-                        }
-                        s => bail!("invalid timings output specifier: `{}`", s),
-                    };
-                    build_config.timing_outputs.push(timing_output);
-                }
-            }
-            if build_config.timing_outputs.is_empty() {
-                build_config.timing_outputs.push(TimingOutput::Html);
-            }
-        }
+        build_config.timing_outputs = self.timing_outputs(gctx)?;
 
         if build_config.build_plan {
             gctx.cli_unstable()
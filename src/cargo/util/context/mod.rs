@@ -177,6 +177,8 @@ pub struct GlobalContext {
     cwd: PathBuf,
     /// Directory where config file searching should stop (inclusive).
     search_stop_path: Option<PathBuf>,
+    /// The only `[lints.cargo]` lints that should run, or every lint if empty
+    lint_allowlist: HashSet<String>,
     /// The location of the cargo executable (path to current process)
     cargo_exe: LazyCell<PathBuf>,
     /// The location of the rustdoc executable
@@ -286,6 +288,7 @@ impl GlobalContext {
             shell: RefCell::new(shell),
             cwd,
             search_stop_path: None,
+            lint_allowlist: HashSet::new(),
             values: LazyCell::new(),
             credential_values: LazyCell::new(),
             cli_config: None,
@@ -565,6 +568,18 @@ impl GlobalContext {
         self.search_stop_path = Some(path);
     }
 
+    /// Restricts the `[lints.cargo]` lints that run to `lints`, regardless of
+    /// the level they are set to. An empty set runs every lint.
+    pub fn set_lint_allowlist(&mut self, lints: HashSet<String>) {
+        self.lint_allowlist = lints;
+    }
+
+    /// The `[lints.cargo]` lints that should run, or an empty set if every
+    /// lint should run
+    pub fn lint_allowlist(&self) -> &HashSet<String> {
+        &self.lint_allowlist
+    }
+
     /// Switches the working directory to [`std::env::current_dir`]
     ///
     /// There is not a need to also call [`Self::reload_rooted_at`].
@@ -584,6 +599,17 @@ impl GlobalContext {
         Ok(())
     }
 
+    /// Adds `--config` values with a lower precedence than those passed on
+    /// the command-line.
+    ///
+    /// The command-line values are already merged into the loaded config, so
+    /// these only take effect on the next [`Self::reload_rooted_at`].
+    pub fn add_default_cli_config(&mut self, cli_config: Vec<String>) {
+        let mut merged = cli_config;
+        merged.extend(self.cli_config.take().unwrap_or_default());
+        self.cli_config = Some(merged);
+    }
+
     /// Reloads on-disk configuration values, starting at the given path and
     /// walking up its ancestors.
     pub fn reload_rooted_at<P: AsRef<Path>>(&mut self, path: P) -> CargoResult<()> {
@@ -1,23 +1,135 @@
+use crate::core::compiler::MessageFormat;
 use crate::core::dependency::DepKind;
-use crate::core::FeatureValue::Dep;
-use crate::core::{Edition, Feature, FeatureValue, Features, Manifest, Package};
+use crate::core::FeatureValue::{Dep, DepFeature};
+use crate::core::{
+    Edition, Feature, FeatureValue, Features, Manifest, MaybePackage, Package, Registry,
+    VirtualManifest, Workspace,
+};
+use crate::sources::source::QueryKind;
+use crate::sources::{PathSource, CRATES_IO_REGISTRY};
+use crate::util::cache_lock::CacheLockMode;
+use crate::util::edit_distance::{closest, edit_distance};
 use crate::util::interning::InternedString;
+use crate::util::machine_message::{self, Message};
+use crate::util::toml::span::get_key_value_span;
+use crate::util::toml::{disallowed_package_keys, DISALLOWED_KEYS};
 use crate::{CargoResult, GlobalContext};
 use annotate_snippets::{Level, Snippet};
-use cargo_util_schemas::manifest::{TomlLintLevel, TomlToolLints};
+use cargo_platform::{CfgExpr, Platform};
+use cargo_util::paths;
+use cargo_util_schemas::manifest::{InheritableDependency, TomlLint, TomlLintLevel, TomlToolLints};
+use itertools::Itertools;
 use pathdiff::diff_paths;
-use std::collections::HashSet;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::ops::Range;
-use std::path::Path;
+use std::path::{Component, Path};
+use std::str::FromStr;
+use std::task::Poll;
 use toml_edit::ImDocument;
 
-const LINT_GROUPS: &[LintGroup] = &[TEST_DUMMY_UNSTABLE];
+const LINT_GROUPS: &[LintGroup] = &[
+    COMPLEXITY,
+    CORRECTNESS,
+    NURSERY,
+    PEDANTIC,
+    PERF,
+    RESTRICTION,
+    STYLE,
+    SUSPICIOUS,
+    TEST_DUMMY_UNSTABLE,
+];
+/// Groups that lints are not declared in, but that apply to a lint based on
+/// the level it would otherwise be set to
+const DYNAMIC_LINT_GROUPS: &[LintGroup] = &[WARNINGS];
 pub const LINTS: &[Lint] = &[
+    BROAD_MEMBERS_GLOB,
+    CFG_TARGET_HAS_LIB_DEPENDENCY_MISMATCH,
+    CONFLICTING_DEPENDENCY_SOURCE,
+    DEEP_PATH_DEPENDENCY,
+    DEPENDENCY_PATH_OUTSIDE_WORKSPACE,
+    DEPENDENCY_REGISTRY_NOT_CONFIGURED,
+    DEPRECATED_MANIFEST_KEY,
+    DUPLICATE_FEATURE_VALUE,
+    FEATURE_ACTIVATES_DEFAULT_OF_OPTIONAL_DEP,
+    FEATURE_BODY_REFERENCES_REMOVED_DEP,
+    FEATURE_ENABLES_OPTIONAL_DEP_WITHOUT_QUESTION_MARK,
     IMPLICIT_FEATURES,
     IM_A_TEAPOT,
+    MANIFEST_BYTE_ORDER_MARK,
+    MANIFEST_INCONSISTENT_INDENTATION,
+    MANIFEST_TRAILING_WHITESPACE,
+    MISPLACED_LINTS_TABLE,
+    OVERLY_BROAD_DEPENDENCY,
+    PACKAGE_FIELD_ORDER,
+    PANIC_SETTING_IN_LIB_PROFILE,
+    PRIVATE_DEPENDENCY_FEATURE,
+    REDUNDANT_DEFAULT_FEATURES_TRUE,
+    REDUNDANT_OPTIONAL_FALSE,
+    REDUNDANT_TARGET_DEPENDENCY,
+    SCRIPT_INCOMPATIBLE_KEY,
+    SELF_REFERENTIAL_FEATURE,
+    SUSPICIOUS_KEY_CASING,
+    TAB_INDENTATION,
+    TARGET_PLATFORM_TRIPLE_TYPO,
+    UNKNOWN_DEPENDENCY_FEATURE,
     UNKNOWN_LINTS,
+    UNKNOWN_PROFILE_FIELD,
+    UNUSED_FEATURE,
     UNUSED_OPTIONAL_DEPENDENCY,
+    UNUSED_WORKSPACE_PACKAGE_FIELD,
+    WILDCARD_DEPENDENCY,
+    WORKSPACE_DEFAULT_MEMBERS_NOT_SUBSET,
+    WORKSPACE_PACKAGE_UNUSED_IN_VIRTUAL_ONLY_WORKSPACE,
+    WORKSPACE_RESOLVER_MISMATCH,
+];
+
+/// Lints that honor `scope` to limit them to a kind of dependency, see
+/// [`Lint::dependency_scope`]
+const DEPENDENCY_SCOPED_LINTS: &[&str] =
+    &[CONFLICTING_DEPENDENCY_SOURCE.name, WILDCARD_DEPENDENCY.name];
+
+/// Whether `option` is a setting that the cargo lint `name` reads from its
+/// `[lints]` entry, rather than an unused key
+pub fn is_lint_option(name: &str, option: &str) -> bool {
+    match option {
+        "scope" => DEPENDENCY_SCOPED_LINTS.contains(&name),
+        "max-depth" => name == DEEP_PATH_DEPENDENCY.name,
+        "order" => name == PACKAGE_FIELD_ORDER.name,
+        "indent-width" => name == MANIFEST_INCONSISTENT_INDENTATION.name,
+        "tab-width" => name == TAB_INDENTATION.name,
+        _ => false,
+    }
+}
+
+/// Rewrites a manifest's `contents` to fix a lint, returning the new contents
+/// and the number of fixes, if there were any
+pub type ManifestFix = fn(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>>;
+
+/// The lints that `cargo fix` can fix in a manifest, in the order the fixes are
+/// applied
+pub const MANIFEST_FIXES: &[(&Lint, ManifestFix)] = &[
+    (&DEPRECATED_MANIFEST_KEY, fix_deprecated_manifest_key),
+    (
+        &REDUNDANT_DEFAULT_FEATURES_TRUE,
+        fix_redundant_default_features_true,
+    ),
+    (&REDUNDANT_OPTIONAL_FALSE, fix_redundant_optional_false),
+    (&SELF_REFERENTIAL_FEATURE, fix_self_referential_feature),
+    (&DUPLICATE_FEATURE_VALUE, fix_duplicate_feature_value),
+    (&TAB_INDENTATION, fix_tab_indentation),
+    (&MANIFEST_BYTE_ORDER_MARK, fix_manifest_byte_order_mark),
+    // Trim whitespace last, in case another fix left some behind
+    (
+        &MANIFEST_TRAILING_WHITESPACE,
+        fix_manifest_trailing_whitespace,
+    ),
 ];
 
 pub fn analyze_cargo_lints_table(
@@ -27,20 +139,50 @@ pub fn analyze_cargo_lints_table(
     ws_contents: &str,
     ws_document: &ImDocument<String>,
     ws_path: &Path,
+    message_format: MessageFormat,
+    report: &mut Vec<String>,
     gctx: &GlobalContext,
 ) -> CargoResult<()> {
     let mut error_count = 0;
     let manifest = pkg.manifest();
     let manifest_path = rel_cwd_manifest_path(path, gctx);
     let ws_path = rel_cwd_manifest_path(ws_path, gctx);
-    let mut unknown_lints = Vec::new();
-    for lint_name in pkg_lints.keys().map(|name| name) {
+    let mut emitted_unknown_source = false;
+    // Report lints in the order they were written, so diagnostics are stable
+    // regardless of how `pkg_lints` orders its keys
+    let lint_names = pkg_lints
+        .keys()
+        .sorted_by_cached_key(|name| {
+            let name = name.as_str();
+            let span = get_span(manifest.document(), &["lints", "cargo", name], false)
+                .or_else(|| get_span(ws_document, &["workspace", "lints", "cargo", name], false));
+            (span.map_or(usize::MAX, |span| span.start), name.to_owned())
+        })
+        .collect::<Vec<_>>();
+    for lint_name in lint_names {
         let Some((name, default_level, edition_lint_opts, feature_gate)) =
             find_lint_or_group(lint_name)
         else {
-            unknown_lints.push(lint_name);
+            output_unknown_lint(
+                lint_name,
+                manifest,
+                &manifest_path,
+                pkg_lints,
+                ws_contents,
+                ws_document,
+                &ws_path,
+                message_format,
+                &mut emitted_unknown_source,
+                report,
+                &mut error_count,
+                gctx,
+            );
             continue;
         };
+        let allowlist = gctx.lint_allowlist();
+        if !allowlist.is_empty() && !allowlist.contains(name) {
+            continue;
+        }
 
         let (_, reason, _) = level_priority(
             name,
@@ -55,6 +197,19 @@ pub fn analyze_cargo_lints_table(
             continue;
         }
 
+        explain_forbidding_groups(
+            name,
+            manifest,
+            &manifest_path,
+            pkg_lints,
+            ws_contents,
+            ws_document,
+            &ws_path,
+            message_format,
+            report,
+            gctx,
+        );
+
         // Only run this on lints that are gated by a feature
         if let Some(feature_gate) = feature_gate {
             verify_feature_enabled(
@@ -65,24 +220,14 @@ pub fn analyze_cargo_lints_table(
                 ws_contents,
                 ws_document,
                 &ws_path,
+                message_format,
+                report,
                 &mut error_count,
                 gctx,
-            )?;
+            );
         }
     }
 
-    output_unknown_lints(
-        unknown_lints,
-        manifest,
-        &manifest_path,
-        pkg_lints,
-        ws_contents,
-        ws_document,
-        &ws_path,
-        &mut error_count,
-        gctx,
-    )?;
-
     if error_count > 0 {
         Err(anyhow::anyhow!(
             "encountered {error_count} errors(s) while verifying lints",
@@ -97,7 +242,7 @@ fn find_lint_or_group<'a>(
 ) -> Option<(
     &'static str,
     &LintLevel,
-    &Option<(Edition, LintLevel)>,
+    &&'static [(Edition, LintLevel)],
     &Option<&'static Feature>,
 )> {
     if let Some(lint) = LINTS.iter().find(|l| l.name == name) {
@@ -107,7 +252,11 @@ fn find_lint_or_group<'a>(
             &lint.edition_lint_opts,
             &lint.feature_gate,
         ))
-    } else if let Some(group) = LINT_GROUPS.iter().find(|g| g.name == name) {
+    } else if let Some(group) = LINT_GROUPS
+        .iter()
+        .chain(DYNAMIC_LINT_GROUPS)
+        .find(|g| g.name == name)
+    {
         Some((
             group.name,
             &group.default_level,
@@ -127,12 +276,40 @@ fn verify_feature_enabled(
     ws_contents: &str,
     ws_document: &ImDocument<String>,
     ws_path: &str,
+    message_format: MessageFormat,
+    report: &mut Vec<String>,
     error_count: &mut usize,
     gctx: &GlobalContext,
-) -> CargoResult<()> {
+) {
     if !manifest.unstable_features().is_enabled(feature_gate) {
         let dash_feature_name = feature_gate.name().replace("_", "-");
         let title = format!("use of unstable lint `{}`", lint_name);
+        *error_count += 1;
+
+        if let MessageFormat::Short = message_format {
+            let location = if let Some(span) =
+                get_span(manifest.document(), &["lints", "cargo", lint_name], false)
+            {
+                Some((manifest.contents(), manifest_path, span))
+            } else if let Some(span) = get_span(
+                ws_document,
+                &["workspace", "lints", "cargo", lint_name],
+                false,
+            ) {
+                Some((ws_contents, ws_path, span))
+            } else {
+                None
+            };
+            report.push(render_short(
+                manifest_path,
+                location,
+                Level::Error,
+                lint_name,
+                &title,
+            ));
+            return;
+        }
+
         let label = format!(
             "this is behind `{}`, which is not enabled",
             dash_feature_name
@@ -155,16 +332,11 @@ fn verify_feature_enabled(
                         .fold(true),
                 )
                 .footer(Level::Help.title(&help))
-        } else {
-            let lint_span = get_span(
-                ws_document,
-                &["workspace", "lints", "cargo", lint_name],
-                false,
-            )
-            .expect(&format!(
-                "could not find `cargo::{lint_name}` in `[lints]`, or `[workspace.lints]` "
-            ));
-
+        } else if let Some(lint_span) = get_span(
+            ws_document,
+            &["workspace", "lints", "cargo", lint_name],
+            false,
+        ) {
             let inherited_note = if let (Some(inherit_span_key), Some(inherit_span_value)) = (
                 get_span(manifest.document(), &["lints", "workspace"], false),
                 get_span(manifest.document(), &["lints", "workspace"], true),
@@ -191,56 +363,251 @@ fn verify_feature_enabled(
                 )
                 .footer(inherited_note)
                 .footer(Level::Help.title(&help))
+        } else {
+            // The lint could not be located in either manifest, so there is
+            // nothing to point at
+            Level::Error.title(&title).footer(Level::Help.title(&help))
         };
 
-        *error_count += 1;
-        gctx.shell().print_message(message)?;
+        report.push(gctx.shell().render_message(message));
     }
-    Ok(())
 }
 
-fn get_span(document: &ImDocument<String>, path: &[&str], get_value: bool) -> Option<Range<usize>> {
-    let mut table = document.as_item().as_table_like()?;
-    let mut iter = path.into_iter().peekable();
-    while let Some(key) = iter.next() {
-        let (key, item) = table.get_key_value(key)?;
-        if iter.peek().is_none() {
-            return if get_value {
-                item.span()
-            } else {
-                let leaf_decor = key.dotted_decor();
-                let leaf_prefix_span = leaf_decor.prefix().and_then(|p| p.span());
-                let leaf_suffix_span = leaf_decor.suffix().and_then(|s| s.span());
-                if let (Some(leaf_prefix_span), Some(leaf_suffix_span)) =
-                    (leaf_prefix_span, leaf_suffix_span)
-                {
-                    Some(leaf_prefix_span.start..leaf_suffix_span.end)
-                } else {
-                    key.span()
-                }
-            };
+/// Notes each group that also forbids `lint_name` when the lint is itself set
+/// to `forbid`, as only one of them is named when the lint is reported
+fn explain_forbidding_groups(
+    lint_name: &str,
+    manifest: &Manifest,
+    manifest_path: &str,
+    pkg_lints: &TomlToolLints,
+    ws_contents: &str,
+    ws_document: &ImDocument<String>,
+    ws_path: &str,
+    message_format: MessageFormat,
+    report: &mut Vec<String>,
+    gctx: &GlobalContext,
+) {
+    let Some(lint) = LINTS.iter().find(|l| l.name == lint_name) else {
+        return;
+    };
+    let edition = manifest.edition();
+    let (level, reason, _) = level_priority(
+        lint.name,
+        lint.default_level,
+        lint.edition_lint_opts,
+        pkg_lints,
+        edition,
+    );
+    if level != LintLevel::Forbid || !reason.is_user_specified() {
+        return;
+    }
+
+    // Lints are either all set in the package or all inherited from the
+    // workspace, so the lint and its groups are in the same manifest
+    let (contents, document, origin, table): (_, _, _, &[&str]) =
+        if get_span(manifest.document(), &["lints", "cargo", lint.name], false).is_some() {
+            (
+                manifest.contents(),
+                manifest.document(),
+                manifest_path,
+                &["lints", "cargo"],
+            )
+        } else {
+            (
+                ws_contents,
+                ws_document,
+                ws_path,
+                &["workspace", "lints", "cargo"],
+            )
+        };
+    let span = |name: &'static str| {
+        let mut path = table.to_vec();
+        path.push(name);
+        get_span(document, &path, false)
+    };
+
+    for group in lint.groups {
+        let (group_level, group_reason, _) = level_priority(
+            group.name,
+            group.default_level,
+            group.edition_lint_opts,
+            pkg_lints,
+            edition,
+        );
+        if group_level != LintLevel::Forbid || !group_reason.is_user_specified() {
+            continue;
         }
-        if item.is_table_like() {
-            table = item.as_table_like().unwrap();
+        let title = format!(
+            "`cargo::{}` is also forbidden by `cargo::{}`",
+            lint.name, group.name
+        );
+
+        if let MessageFormat::Short = message_format {
+            let location = span(group.name).map(|span| (contents, origin, span));
+            report.push(render_short(
+                manifest_path,
+                location,
+                Level::Note,
+                lint.name,
+                &title,
+            ));
+            continue;
         }
-        if item.is_array() && iter.peek().is_some() {
-            let array = item.as_array().unwrap();
-            let next = iter.next().unwrap();
-            return array.iter().find_map(|item| {
-                if next == &item.to_string() {
-                    item.span()
-                } else {
-                    None
-                }
-            });
+
+        let group_label = format!("this also forbids `cargo::{}`", lint.name);
+        let message = match (span(group.name), span(lint.name)) {
+            (Some(group_span), Some(lint_span)) => Level::Note.title(&title).snippet(
+                Snippet::source(contents)
+                    .origin(origin)
+                    .annotation(Level::Note.span(group_span).label(&group_label))
+                    .annotation(
+                        Level::Note
+                            .span(lint_span)
+                            .label("the lint's own `forbid` is the one reported"),
+                    )
+                    .fold(true),
+            ),
+            _ => Level::Note.title(&title),
+        };
+        report.push(gctx.shell().render_message(message));
+    }
+}
+
+/// Renders a diagnostic for `--message-format=short` as
+/// `path:line:col: level: lint_name: message`
+///
+/// `location` is the `(contents, path, span)` the diagnostic points at. If it
+/// is unknown, only `origin` is shown.
+fn render_short(
+    origin: &str,
+    location: Option<(&str, &str, Range<usize>)>,
+    level: Level,
+    lint_name: &str,
+    message: &str,
+) -> String {
+    let level = match level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Info => "info",
+        Level::Note => "note",
+        Level::Help => "help",
+    };
+    match location {
+        Some((contents, path, span)) => {
+            let (line, col) = line_col(contents, span.start);
+            format!("{path}:{line}:{col}: {level}: {lint_name}: {message}")
         }
+        None => format!("{origin}: {level}: {lint_name}: {message}"),
+    }
+}
+
+/// Converts the byte offset `index` into `contents` to a 1-based line and
+/// column, with the column counted in characters
+fn line_col(contents: &str, index: usize) -> (usize, usize) {
+    let before = &contents[..index];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let col = before[line_start..].chars().count() + 1;
+    (line, col)
+}
+
+/// Prints a diagnostic for `--message-format json`
+fn print_json(
+    gctx: &GlobalContext,
+    lint: &Lint,
+    lint_level: LintLevel,
+    path: &Path,
+    span: Range<usize>,
+    message: &str,
+) {
+    let msg = machine_message::ManifestLint {
+        manifest_path: path.to_path_buf(),
+        name: lint.name,
+        level: lint_level.to_string(),
+        message,
+        span: Some(span),
+        applicability: lint.applicability,
+        since: lint.since,
+        suggestion: None,
+    }
+    .to_json_string();
+    crate::drop_println!(gctx, "{}", msg);
+}
+
+fn get_span(document: &ImDocument<String>, path: &[&str], get_value: bool) -> Option<Range<usize>> {
+    let span = get_key_value_span(document, path)?;
+    Some(if get_value { span.value } else { span.key })
+}
+
+/// Builds the snippet of `source` a lint diagnostic points at
+///
+/// `span` is the primary location, annotated at `level`. Each of `secondary`
+/// is annotated as a labeled note, for diagnostics that relate several places
+/// in the manifest to each other.
+fn lint_snippet<'a>(
+    level: Level,
+    source: &'a str,
+    origin: &'a str,
+    span: Range<usize>,
+    secondary: impl IntoIterator<Item = (Range<usize>, &'a str)>,
+) -> Snippet<'a> {
+    Snippet::source(source)
+        .origin(origin)
+        .annotation(level.span(span))
+        .annotations(
+            secondary
+                .into_iter()
+                .map(|(span, label)| Level::Note.span(span).label(label)),
+        )
+        .fold(true)
+}
+
+/// Checks if the line directly above `span` is a `# cargo::allow(<lint>)`
+/// comment, which suppresses only the occurrence of `lint` at `span`
+fn is_allowed_by_comment(contents: &str, span: &Range<usize>, lint: &Lint) -> bool {
+    let line_start = contents[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    if line_start == 0 {
+        return false;
     }
-    None
+    let prev_line = contents[..line_start - 1]
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default();
+    let Some(directive) = prev_line.trim().strip_prefix('#') else {
+        return false;
+    };
+    let Some(lints) = directive
+        .trim()
+        .strip_prefix("cargo::allow(")
+        .and_then(|d| d.strip_suffix(')'))
+    else {
+        return false;
+    };
+    lints
+        .split(',')
+        .any(|name| name.trim().replace("-", "_") == lint.name)
+}
+
+/// Names of the optional dependencies activated through `dep:<name>` in some feature
+fn activated_opt_deps(manifest: &Manifest) -> HashSet<&str> {
+    manifest
+        .resolved_toml()
+        .features()
+        .map(|map| {
+            map.values()
+                .flatten()
+                .filter_map(|f| match FeatureValue::new(InternedString::new(f)) {
+                    Dep { dep_name } => Some(dep_name.as_str()),
+                    _ => None,
+                })
+                .collect::<HashSet<_>>()
+        })
+        .unwrap_or_default()
 }
 
 /// Gets the relative path to a manifest from the current working directory, or
 /// the absolute path of the manifest if a relative path cannot be constructed
-fn rel_cwd_manifest_path(path: &Path, gctx: &GlobalContext) -> String {
+pub(crate) fn rel_cwd_manifest_path(path: &Path, gctx: &GlobalContext) -> String {
     diff_paths(path, gctx.cwd())
         .unwrap_or_else(|| path.to_path_buf())
         .display()
@@ -252,16 +619,89 @@ pub struct LintGroup {
     pub name: &'static str,
     pub default_level: LintLevel,
     pub desc: &'static str,
-    pub edition_lint_opts: Option<(Edition, LintLevel)>,
+    pub edition_lint_opts: &'static [(Edition, LintLevel)],
     pub feature_gate: Option<&'static Feature>,
 }
 
+const COMPLEXITY: LintGroup = LintGroup {
+    name: "complexity",
+    desc: "code that does something simple but in a complex way",
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+const CORRECTNESS: LintGroup = LintGroup {
+    name: "correctness",
+    desc: "code that is outright wrong or useless",
+    default_level: LintLevel::Deny,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+const NURSERY: LintGroup = LintGroup {
+    name: "nursery",
+    desc: "new lints that are still under development",
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+const PEDANTIC: LintGroup = LintGroup {
+    name: "pedantic",
+    desc: "lints which are rather strict or have occasional false positives",
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+const PERF: LintGroup = LintGroup {
+    name: "perf",
+    desc: "code that can be written to run faster",
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+const RESTRICTION: LintGroup = LintGroup {
+    name: "restriction",
+    desc: "lints which prevent the use of Cargo features",
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+const STYLE: LintGroup = LintGroup {
+    name: "style",
+    desc: "code that should be written in a more idiomatic way",
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+const SUSPICIOUS: LintGroup = LintGroup {
+    name: "suspicious",
+    desc: "code that is most likely wrong or useless",
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
+/// Applies to every lint that would otherwise be at `warn`
+const WARNINGS: LintGroup = LintGroup {
+    name: "warnings",
+    desc: "lints that are set to warn",
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+};
+
 /// This lint group is only to be used for testing purposes
 const TEST_DUMMY_UNSTABLE: LintGroup = LintGroup {
     name: "test_dummy_unstable",
     desc: "test_dummy_unstable is meant to only be used in tests",
     default_level: LintLevel::Allow,
-    edition_lint_opts: None,
+    edition_lint_opts: &[],
     feature_gate: Some(Feature::test_dummy_unstable()),
 };
 
@@ -271,34 +711,188 @@ pub struct Lint {
     pub desc: &'static str,
     pub groups: &'static [LintGroup],
     pub default_level: LintLevel,
-    pub edition_lint_opts: Option<(Edition, LintLevel)>,
+    /// The level the lint is set to starting in a given edition. When
+    /// multiple editions apply, the latest one takes precedence.
+    pub edition_lint_opts: &'static [(Edition, LintLevel)],
     pub feature_gate: Option<&'static Feature>,
+    /// How confident the lint is in the fix or suggestion it offers
+    pub applicability: Applicability,
+    /// Whether the lint queries a registry index, in which case it does not
+    /// run when `cargo` is offline
+    pub needs_registry: bool,
+    /// The version of Cargo the lint was added in
+    pub since: &'static str,
     /// This is a markdown formatted string that will be used when generating
     /// the lint documentation. If docs is `None`, the lint will not be
     /// documented.
     pub docs: Option<&'static str>,
 }
 
+/// How confident a lint is in the fix or suggestion it offers, mirroring
+/// rustc's applicability levels
+///
+/// Tooling can use this to decide whether to apply a fix without asking.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum Applicability {
+    /// The fix is definitely what the user intended, and `cargo fix` applies it
+    MachineApplicable,
+    /// The suggestion may be what the user intended, but it is uncertain
+    MaybeIncorrect,
+    /// The suggestion contains placeholders that the user has to fill in
+    HasPlaceholders,
+    /// The lint offers no fix, or its applicability is unknown
+    Unspecified,
+}
+
 impl Lint {
+    /// Whether this lint only runs when a `cargo-features` entry is enabled
+    pub fn is_feature_gated(&self) -> bool {
+        self.feature_gate.is_some()
+    }
+
+    /// The name of the `cargo-features` entry this lint is gated behind, if any
+    pub fn feature_name(&self) -> Option<&'static str> {
+        self.feature_gate.map(|f| f.name())
+    }
+
+    /// The kind of dependencies the lint is limited to, set with
+    /// `scope = "normal" | "dev" | "build"` in its `[lints]` entry
+    ///
+    /// Only lints that check dependencies honor this.
+    pub fn dependency_scope(&self, pkg_lints: &TomlToolLints) -> CargoResult<Option<DepKind>> {
+        let Some(TomlLint::Config(config)) = pkg_lints.get(self.name) else {
+            return Ok(None);
+        };
+        let Some(scope) = config.config.get("scope") else {
+            return Ok(None);
+        };
+        match scope.as_str() {
+            Some("normal") => Ok(Some(DepKind::Normal)),
+            Some("dev") => Ok(Some(DepKind::Development)),
+            Some("build") => Ok(Some(DepKind::Build)),
+            _ => anyhow::bail!(
+                "`scope` for `cargo::{}` must be `normal`, `dev`, or `build`, but found {scope}",
+                self.name
+            ),
+        }
+    }
+
+    /// The non-negative integer `option` set in the lint's `[lints]` entry, or
+    /// `default` if it is not set
+    pub fn integer_option(
+        &self,
+        pkg_lints: &TomlToolLints,
+        option: &str,
+        default: u64,
+    ) -> CargoResult<u64> {
+        let Some(TomlLint::Config(config)) = pkg_lints.get(self.name) else {
+            return Ok(default);
+        };
+        let Some(value) = config.config.get(option) else {
+            return Ok(default);
+        };
+        match value.as_integer().map(u64::try_from) {
+            Some(Ok(value)) => Ok(value),
+            _ => anyhow::bail!(
+                "`{option}` for `cargo::{}` must be a non-negative integer, but found {value}",
+                self.name
+            ),
+        }
+    }
+
+    /// The array of strings `option` set in the lint's `[lints]` entry, or
+    /// `default` if it is not set
+    pub fn string_list_option(
+        &self,
+        pkg_lints: &TomlToolLints,
+        option: &str,
+        default: &[&str],
+    ) -> CargoResult<Vec<String>> {
+        let default = || default.iter().map(|s| s.to_string()).collect();
+        let Some(TomlLint::Config(config)) = pkg_lints.get(self.name) else {
+            return Ok(default());
+        };
+        let Some(value) = config.config.get(option) else {
+            return Ok(default());
+        };
+        let list = value.as_array().and_then(|values| {
+            values
+                .iter()
+                .map(|v| v.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+        });
+        match list {
+            Some(list) => Ok(list),
+            None => anyhow::bail!(
+                "`{option}` for `cargo::{}` must be an array of strings, but found {value}",
+                self.name
+            ),
+        }
+    }
+
     pub fn level(
         &self,
         pkg_lints: &TomlToolLints,
         edition: Edition,
         unstable_features: &Features,
     ) -> (LintLevel, LintLevelReason) {
+        let (level, reason, _) = self.level_with_candidates(pkg_lints, edition, unstable_features);
+        (level, reason)
+    }
+
+    /// Like [`Lint::level`], but also returns every candidate level that was
+    /// considered, with the one that was chosen marked as selected
+    ///
+    /// Lints left out of [`GlobalContext::lint_allowlist`] are always `Allow`.
+    pub fn level_with_trace(
+        &self,
+        pkg_lints: &TomlToolLints,
+        edition: Edition,
+        unstable_features: &Features,
+        gctx: &GlobalContext,
+    ) -> (LintLevel, LintLevelReason, Vec<LevelCandidate>) {
+        if !self.is_selected(gctx) {
+            return (LintLevel::Allow, LintLevelReason::Default, Vec::new());
+        }
+        self.level_with_candidates(pkg_lints, edition, unstable_features)
+    }
+
+    /// Whether `edition` changes the level of this lint, or of one of its
+    /// groups, so migrating to it with `cargo fix --edition` should apply
+    /// this lint's fix
+    pub fn is_edition_lint(&self, edition: Edition) -> bool {
+        let changes_in = |opts: &[(Edition, LintLevel)]| opts.iter().any(|(e, _)| *e == edition);
+        changes_in(self.edition_lint_opts)
+            || self.groups.iter().any(|g| changes_in(g.edition_lint_opts))
+    }
+
+    /// Whether this lint should run, according to
+    /// [`GlobalContext::lint_allowlist`]
+    pub fn is_selected(&self, gctx: &GlobalContext) -> bool {
+        let allowlist = gctx.lint_allowlist();
+        allowlist.is_empty() || allowlist.contains(self.name)
+    }
+
+    fn level_with_candidates(
+        &self,
+        pkg_lints: &TomlToolLints,
+        edition: Edition,
+        unstable_features: &Features,
+    ) -> (LintLevel, LintLevelReason, Vec<LevelCandidate>) {
         // We should return `Allow` if a lint is behind a feature, but it is
         // not enabled, that way the lint does not run.
         if self
             .feature_gate
             .is_some_and(|f| !unstable_features.is_enabled(f))
         {
-            return (LintLevel::Allow, LintLevelReason::Default);
+            return (LintLevel::Allow, LintLevelReason::Default, Vec::new());
         }
 
-        self.groups
+        let mut candidates = self
+            .groups
             .iter()
             .map(|g| {
-                (
+                LevelCandidate::new(
                     g.name,
                     level_priority(
                         g.name,
@@ -309,7 +903,7 @@ impl Lint {
                     ),
                 )
             })
-            .chain(std::iter::once((
+            .chain(std::iter::once(LevelCandidate::new(
                 self.name,
                 level_priority(
                     self.name,
@@ -319,10 +913,128 @@ impl Lint {
                     edition,
                 ),
             )))
-            .max_by_key(|(n, (l, _, p))| (l == &LintLevel::Forbid, *p, std::cmp::Reverse(*n)))
-            .map(|(_, (l, r, _))| (l, r))
-            .unwrap()
+            .collect::<Vec<_>>();
+
+        // The lint itself is always the last candidate. On equal priority, a
+        // level from `[lints]` beats a default one, and the lint wins ties with
+        // its groups
+        let lint_index = candidates.len() - 1;
+        let (selected, winner) = candidates
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, c)| {
+                (
+                    c.level == LintLevel::Forbid,
+                    c.priority,
+                    c.reason.is_user_specified(),
+                    *i == lint_index,
+                    std::cmp::Reverse(c.name),
+                )
+            })
+            .unwrap();
+        let others = || {
+            candidates
+                .iter()
+                .enumerate()
+                .filter(move |(i, _)| *i != selected)
+                .map(|(_, c)| c)
+        };
+        let ties = || others().filter(|c| c.priority == winner.priority);
+        let rule = if winner.level == LintLevel::Forbid {
+            Some("`forbid` cannot be overridden")
+        } else if others().next().is_none() {
+            None
+        } else if ties().next().is_none() {
+            Some("it has the highest priority")
+        } else if winner.reason.is_user_specified() && ties().all(|c| !c.reason.is_user_specified())
+        {
+            Some("it is specified in `[lints]` and the others with its priority are not")
+        } else if selected == lint_index {
+            Some("a lint wins ties with its groups")
+        } else {
+            Some("it comes first by name among the groups with its priority")
+        };
+        let (mut level, mut reason) = (winner.level, winner.reason);
+        candidates[selected].selected = true;
+        candidates[selected].rule = rule;
+
+        // A lint's own entry in `[lints]` takes precedence over `warnings`
+        if level == LintLevel::Warn && !candidates[lint_index].reason.is_user_specified() {
+            let (warnings_level, warnings_reason, priority) = level_priority(
+                WARNINGS.name,
+                WARNINGS.default_level,
+                WARNINGS.edition_lint_opts,
+                pkg_lints,
+                edition,
+            );
+            if warnings_reason.is_user_specified() {
+                candidates[selected].selected = false;
+                candidates[selected].rule = None;
+                let mut candidate =
+                    LevelCandidate::new(WARNINGS.name, (warnings_level, warnings_reason, priority));
+                candidate.selected = true;
+                candidate.rule = Some("it applies to lints that would otherwise warn");
+                (level, reason) = (warnings_level, warnings_reason);
+                candidates.push(candidate);
+            }
+        }
+
+        (level, reason, candidates)
+    }
+}
+
+/// A level that was considered for a lint, coming from either the lint itself
+/// or one of its groups
+#[derive(Clone, Debug, PartialEq)]
+pub struct LevelCandidate {
+    pub name: &'static str,
+    pub level: LintLevel,
+    pub reason: LintLevelReason,
+    pub priority: i8,
+    /// Whether this candidate decided the lint's level
+    pub selected: bool,
+    /// Why this candidate won out over the others, if it was selected
+    pub rule: Option<&'static str>,
+}
+
+impl LevelCandidate {
+    fn new(
+        name: &'static str,
+        (level, reason, priority): (LintLevel, LintLevelReason, i8),
+    ) -> Self {
+        LevelCandidate {
+            name,
+            level,
+            reason,
+            priority,
+            selected: false,
+            rule: None,
+        }
+    }
+}
+
+impl Display for LevelCandidate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "considered `cargo::{}`: `{}` {} with priority {}",
+            self.name, self.level, self.reason, self.priority
+        )?;
+        match (self.selected, self.rule) {
+            (true, Some(rule)) => write!(f, " (selected, as {rule})"),
+            (true, None) => write!(f, " (selected)"),
+            (false, _) => Ok(()),
+        }
+    }
+}
+
+/// Renders the candidates considered for a lint's level when
+/// `-Zexplain-lint-levels` is enabled
+fn level_trace_notes(trace: &[LevelCandidate], gctx: &GlobalContext) -> Vec<String> {
+    if !gctx.cli_unstable().explain_lint_levels {
+        return Vec::new();
     }
+    trace.iter().map(|c| c.to_string()).collect()
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -344,13 +1056,40 @@ impl Display for LintLevel {
     }
 }
 
+impl FromStr for LintLevel {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "allow" => Ok(LintLevel::Allow),
+            "warn" => Ok(LintLevel::Warn),
+            "deny" => Ok(LintLevel::Deny),
+            "forbid" => Ok(LintLevel::Forbid),
+            _ => anyhow::bail!(
+                "supported lint levels are `allow`, `warn`, `deny`, or `forbid`, \
+                 but `{s}` is unknown"
+            ),
+        }
+    }
+}
+
+impl TryFrom<&str> for LintLevel {
+    type Error = anyhow::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 impl LintLevel {
-    pub fn to_diagnostic_level(self) -> Level {
+    /// Returns the [`Level`] a lint set to this level should be reported at, or
+    /// `None` for [`LintLevel::Allow`] as allowed lints are not reported.
+    pub fn to_diagnostic_level(self) -> Option<Level> {
         match self {
-            LintLevel::Allow => unreachable!("allow does not map to a diagnostic level"),
-            LintLevel::Warn => Level::Warning,
-            LintLevel::Deny => Level::Error,
-            LintLevel::Forbid => Level::Error,
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Level::Warning),
+            LintLevel::Deny => Some(Level::Error),
+            LintLevel::Forbid => Some(Level::Error),
         }
     }
 }
@@ -396,13 +1135,15 @@ impl LintLevelReason {
 fn level_priority(
     name: &str,
     default_level: LintLevel,
-    edition_lint_opts: Option<(Edition, LintLevel)>,
+    edition_lint_opts: &[(Edition, LintLevel)],
     pkg_lints: &TomlToolLints,
     edition: Edition,
 ) -> (LintLevel, LintLevelReason, i8) {
     let (unspecified_level, reason) = if let Some(level) = edition_lint_opts
+        .iter()
         .filter(|(e, _)| edition >= *e)
-        .map(|(_, l)| l)
+        .max_by_key(|(e, _)| *e)
+        .map(|(_, l)| *l)
     {
         (level, LintLevelReason::Edition(edition))
     } else {
@@ -425,116 +1166,168 @@ fn level_priority(
     }
 }
 
-/// This lint is only to be used for testing purposes
-const IM_A_TEAPOT: Lint = Lint {
-    name: "im_a_teapot",
-    desc: "`im_a_teapot` is specified",
-    groups: &[TEST_DUMMY_UNSTABLE],
+const BROAD_MEMBERS_GLOB: Lint = Lint {
+    name: "broad_members_glob",
+    desc: "`workspace.members` glob searches every directory of the workspace",
+    groups: &[PERF],
     default_level: LintLevel::Allow,
-    edition_lint_opts: None,
-    feature_gate: Some(Feature::test_dummy_unstable()),
-    docs: None,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for `workspace.members` globs that start with `**`, which recurse from
+the workspace root
+
+### Why it is bad
+`cargo` has to walk every directory of the workspace to expand the glob,
+including large ones like `target`, which slows down every command.
+
+### Example
+```toml
+[workspace]
+members = ["**/member-*"]
+```
+
+Should be written as:
+```toml
+[workspace]
+members = ["crates/*"]
+```
+"#,
+    ),
 };
 
-pub fn check_im_a_teapot(
-    pkg: &Package,
-    path: &Path,
-    pkg_lints: &TomlToolLints,
+pub fn check_broad_members_glob(
+    ws: &Workspace<'_>,
     error_count: &mut usize,
     gctx: &GlobalContext,
 ) -> CargoResult<()> {
-    let manifest = pkg.manifest();
-    let (lint_level, reason) =
-        IM_A_TEAPOT.level(pkg_lints, manifest.edition(), manifest.unstable_features());
-
-    if lint_level == LintLevel::Allow {
+    let (contents, document, resolved_ws, unstable_features) = match ws.root_maybe() {
+        MaybePackage::Package(pkg) => {
+            let manifest = pkg.manifest();
+            (
+                manifest.contents(),
+                manifest.document(),
+                manifest.resolved_toml().workspace.as_ref(),
+                manifest.unstable_features(),
+            )
+        }
+        MaybePackage::Virtual(vm) => (
+            vm.contents(),
+            vm.document(),
+            vm.resolved_toml().workspace.as_ref(),
+            vm.unstable_features(),
+        ),
+    };
+    let Some(resolved_ws) = resolved_ws else {
         return Ok(());
-    }
+    };
+    let ws_lints = resolved_ws
+        .lints
+        .as_ref()
+        .and_then(|lints| lints.get("cargo"))
+        .cloned()
+        .unwrap_or_default();
+    let (lint_level, reason, trace) =
+        BROAD_MEMBERS_GLOB.level_with_trace(&ws_lints, Edition::default(), unstable_features, gctx);
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
 
-    if manifest
-        .resolved_toml()
-        .package()
-        .is_some_and(|p| p.im_a_teapot.is_some())
-    {
+    let manifest_path = rel_cwd_manifest_path(ws.root_manifest(), gctx);
+    let mut emitted_source = None;
+    for glob in resolved_ws.members.iter().flatten() {
+        if !is_broad_members_glob(glob) {
+            continue;
+        }
+        let Some(span) = get_span(document, &["workspace", "members", glob.as_str()], true) else {
+            continue;
+        };
+        if is_allowed_by_comment(contents, &span, &BROAD_MEMBERS_GLOB) {
+            continue;
+        }
         if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
             *error_count += 1;
         }
-        let level = lint_level.to_diagnostic_level();
-        let manifest_path = rel_cwd_manifest_path(path, gctx);
-        let emitted_reason = format!(
-            "`cargo::{}` is set to `{lint_level}` {reason}",
-            IM_A_TEAPOT.name
+        let title = format!("`{glob}` searches every directory of the workspace for members");
+        let mut message =
+            level
+                .title(&title)
+                .snippet(lint_snippet(level, contents, &manifest_path, span, []));
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                BROAD_MEMBERS_GLOB.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(
+            Level::Help
+                .title("narrow the glob to the directories that contain members, like `crates/*`"),
         );
 
-        let key_span = get_span(manifest.document(), &["package", "im-a-teapot"], false).unwrap();
-        let value_span = get_span(manifest.document(), &["package", "im-a-teapot"], true).unwrap();
-        let message = level
-            .title(IM_A_TEAPOT.desc)
-            .snippet(
-                Snippet::source(manifest.contents())
-                    .origin(&manifest_path)
-                    .annotation(level.span(key_span.start..value_span.end))
-                    .fold(true),
-            )
-            .footer(Level::Note.title(&emitted_reason));
-
         gctx.shell().print_message(message)?;
     }
     Ok(())
 }
 
-const IMPLICIT_FEATURES: Lint = Lint {
-    name: "implicit_features",
-    desc: "implicit features for optional dependencies is deprecated and will be unavailable in the 2024 edition",
-    groups: &[],
-    default_level: LintLevel::Allow,
-    edition_lint_opts: None,
+/// Whether the `workspace.members` entry `glob` recurses from the workspace
+/// root, like `**` or `**/member-*`
+fn is_broad_members_glob(glob: &str) -> bool {
+    let glob = glob.trim_start_matches("./");
+    glob.split(['/', '\\']).next() == Some("**")
+}
+
+const CFG_TARGET_HAS_LIB_DEPENDENCY_MISMATCH: Lint = Lint {
+    name: "cfg_target_has_lib_dependency_mismatch",
+    desc: "dependency has conflicting settings across target tables",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
     feature_gate: None,
-    docs: Some(r#"
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
 ### What it does
-Checks for implicit features for optional dependencies
+Checks for a dependency that is declared in multiple
+`[target.*.dependencies]` tables with different `optional` or
+`default-features` settings
 
 ### Why it is bad
-By default, cargo will treat any optional dependency as a [feature]. As of
-cargo 1.60, these can be disabled by declaring a feature that activates the
-optional dependency as `dep:<name>` (see [RFC #3143]).
-
-In the 2024 edition, `cargo` will stop exposing optional dependencies as
-features implicitly, requiring users to add `foo = ["dep:foo"]` if they
-still want it exposed.
-
-For more information, see [RFC #3491]
+When more than one of the target tables applies, the declarations are unified
+and the resulting behavior depends on which one `cargo` sees first.
 
 ### Example
 ```toml
-edition = "2021"
-
-[dependencies]
+[target.'cfg(unix)'.dependencies]
 bar = { version = "0.1.0", optional = true }
 
-[features]
-# No explicit feature activation for `bar`
+[target.'cfg(target_os = "linux")'.dependencies]
+bar = "0.1.0"
 ```
 
-Instead, the dependency should have an explicit feature:
+Should be written as:
 ```toml
-edition = "2021"
-
-[dependencies]
+[target.'cfg(unix)'.dependencies]
 bar = { version = "0.1.0", optional = true }
 
-[features]
-bar = ["dep:bar"]
+[target.'cfg(target_os = "linux")'.dependencies]
+bar = { version = "0.1.0", optional = true }
 ```
-
-[feature]: https://doc.rust-lang.org/cargo/reference/features.html
-[RFC #3143]: https://rust-lang.github.io/rfcs/3143-cargo-weak-namespaced-features.html
-[RFC #3491]: https://rust-lang.github.io/rfcs/3491-remove-implicit-features.html
-"#
+"#,
     ),
 };
 
-pub fn check_implicit_features(
+pub fn check_cfg_target_has_lib_dependency_mismatch(
     pkg: &Package,
     path: &Path,
     pkg_lints: &TomlToolLints,
@@ -542,242 +1335,400 @@ pub fn check_implicit_features(
     gctx: &GlobalContext,
 ) -> CargoResult<()> {
     let manifest = pkg.manifest();
-    let edition = manifest.edition();
-    // In Edition 2024+, instead of creating optional features, the dependencies are unused.
-    // See `UNUSED_OPTIONAL_DEPENDENCY`
-    if edition >= Edition::Edition2024 {
+    let (lint_level, reason, trace) = CFG_TARGET_HAS_LIB_DEPENDENCY_MISMATCH.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
         return Ok(());
-    }
+    };
 
-    let (lint_level, reason) =
-        IMPLICIT_FEATURES.level(pkg_lints, edition, manifest.unstable_features());
-    if lint_level == LintLevel::Allow {
-        return Ok(());
+    // Every declaration of a dependency in a `[target.*.dependencies]` table,
+    // as `(platform, optional, default-features, span)`
+    let mut declarations: HashMap<&str, Vec<(&str, bool, bool, Range<usize>)>> = HashMap::new();
+    for (platform, target) in manifest.resolved_toml().target.iter().flatten() {
+        for (name, dep) in target.dependencies.iter().flatten() {
+            let InheritableDependency::Value(dep) = dep else {
+                continue;
+            };
+            let toml_path = ["target", platform.as_str(), "dependencies", name.as_str()];
+            let Some(span) = get_span(manifest.document(), &toml_path, false) else {
+                continue;
+            };
+            declarations.entry(name.as_str()).or_default().push((
+                platform.as_str(),
+                dep.is_optional(),
+                dep.default_features().unwrap_or(true),
+                span,
+            ));
+        }
     }
 
-    let activated_opt_deps = manifest
-        .resolved_toml()
-        .features()
-        .map(|map| {
-            map.values()
-                .flatten()
-                .filter_map(|f| match FeatureValue::new(InternedString::new(f)) {
-                    Dep { dep_name } => Some(dep_name.as_str()),
-                    _ => None,
-                })
-                .collect::<HashSet<_>>()
-        })
-        .unwrap_or_default();
-
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
     let mut emitted_source = None;
-    for dep in manifest.dependencies() {
-        let dep_name_in_toml = dep.name_in_toml();
-        if !dep.is_optional() || activated_opt_deps.contains(dep_name_in_toml.as_str()) {
+    for (name, mut declarations) in declarations.into_iter().sorted_by_key(|(name, _)| *name) {
+        // Anchor on later declarations, in the order they are written
+        declarations.sort_by_key(|(_, _, _, span)| span.start);
+        let Some(((first_platform, first_optional, first_default_features, first_span), rest)) =
+            declarations.split_first()
+        else {
             continue;
-        }
-        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
-            *error_count += 1;
-        }
-        let mut toml_path = vec![dep.kind().kind_table(), dep_name_in_toml.as_str()];
-        let platform = dep.platform().map(|p| p.to_string());
-        if let Some(platform) = platform.as_ref() {
-            toml_path.insert(0, platform);
-            toml_path.insert(0, "target");
-        }
-        let level = lint_level.to_diagnostic_level();
-        let manifest_path = rel_cwd_manifest_path(path, gctx);
-        let mut message = level.title(IMPLICIT_FEATURES.desc).snippet(
-            Snippet::source(manifest.contents())
-                .origin(&manifest_path)
-                .annotation(level.span(get_span(manifest.document(), &toml_path, false).unwrap()))
-                .fold(true),
-        );
-        if emitted_source.is_none() {
-            emitted_source = Some(format!(
-                "`cargo::{}` is set to `{lint_level}` {reason}",
-                IMPLICIT_FEATURES.name
+        };
+        for (_, optional, default_features, span) in rest {
+            let mut fields = Vec::new();
+            if optional != first_optional {
+                fields.push("`optional`");
+            }
+            if default_features != first_default_features {
+                fields.push("`default-features`");
+            }
+            if fields.is_empty() {
+                continue;
+            }
+            if is_allowed_by_comment(
+                manifest.contents(),
+                span,
+                &CFG_TARGET_HAS_LIB_DEPENDENCY_MISMATCH,
+            ) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let title = format!(
+                "`{name}` sets {} differently than in `[target.{}.dependencies]`",
+                fields.join(" and "),
+                toml_edit::Key::new(*first_platform),
+            );
+            let mut message = level.title(&title).snippet(lint_snippet(
+                level,
+                manifest.contents(),
+                &manifest_path,
+                span.clone(),
+                [(first_span.clone(), "first declared here")],
             ));
-            message = message.footer(Level::Note.title(emitted_source.as_ref().unwrap()));
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    CFG_TARGET_HAS_LIB_DEPENDENCY_MISMATCH.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            let help = format!("use the same {} for every target", fields.join(" and "));
+            message = message.footer(Level::Help.title(&help));
+
+            gctx.shell().print_message(message)?;
         }
-        gctx.shell().print_message(message)?;
     }
     Ok(())
 }
 
-const UNKNOWN_LINTS: Lint = Lint {
-    name: "unknown_lints",
-    desc: "unknown lint",
-    groups: &[],
-    default_level: LintLevel::Warn,
-    edition_lint_opts: None,
+const CONFLICTING_DEPENDENCY_SOURCE: Lint = Lint {
+    name: "conflicting_dependency_source",
+    desc: "dependency specifies a source that is ignored",
+    groups: &[CORRECTNESS],
+    default_level: LintLevel::Deny,
+    edition_lint_opts: &[],
     feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
     docs: Some(
         r#"
 ### What it does
-Checks for unknown lints in the `[lints.cargo]` table
+Checks for dependencies that specify both `path` and `registry` (or
+`registry-index`) without a `version`
+
+Other combinations, like `git` with `path` or `registry`, are rejected when
+the manifest is loaded.
 
 ### Why it is bad
-- The lint name could be misspelled, leading to confusion as to why it is
-  not working as expected
-- The unknown lint could end up causing an error if `cargo` decides to make
-  a lint with the same name in the future
+`cargo` builds the dependency from `path`. The registry is only used when
+publishing, to look up the `version` requirement, so without one it is never
+used and publishing fails.
 
 ### Example
 ```toml
-[lints.cargo]
-this-lint-does-not-exist = "warn"
+[dependencies]
+bar = { path = "../bar", registry = "my-registry" }
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { path = "../bar", version = "0.1.0", registry = "my-registry" }
 ```
 "#,
     ),
 };
 
-fn output_unknown_lints(
-    unknown_lints: Vec<&String>,
-    manifest: &Manifest,
-    manifest_path: &str,
+pub fn check_conflicting_dependency_source(
+    pkg: &Package,
+    path: &Path,
     pkg_lints: &TomlToolLints,
-    ws_contents: &str,
-    ws_document: &ImDocument<String>,
-    ws_path: &str,
     error_count: &mut usize,
     gctx: &GlobalContext,
 ) -> CargoResult<()> {
-    let (lint_level, reason) =
-        UNKNOWN_LINTS.level(pkg_lints, manifest.edition(), manifest.unstable_features());
-    if lint_level == LintLevel::Allow {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = CONFLICTING_DEPENDENCY_SOURCE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
         return Ok(());
-    }
+    };
+
+    let scope = CONFLICTING_DEPENDENCY_SOURCE.dependency_scope(pkg_lints)?;
 
-    let level = lint_level.to_diagnostic_level();
+    let document = manifest.document();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
     let mut emitted_source = None;
-    for lint_name in unknown_lints {
-        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
-            *error_count += 1;
+    for (table_path, deps) in dependency_tables(document) {
+        if scope.is_some_and(|scope| dependency_table_kind(&table_path) != scope) {
+            continue;
         }
-        let title = format!("{}: `{lint_name}`", UNKNOWN_LINTS.desc);
-        let second_title = format!("`cargo::{}` was inherited", lint_name);
-        let underscore_lint_name = lint_name.replace("-", "_");
-        let matching = if let Some(lint) = LINTS.iter().find(|l| l.name == underscore_lint_name) {
-            Some((lint.name, "lint"))
-        } else if let Some(group) = LINT_GROUPS.iter().find(|g| g.name == underscore_lint_name) {
-            Some((group.name, "group"))
-        } else {
-            None
-        };
-        let help =
-            matching.map(|(name, kind)| format!("there is a {kind} with a similar name: `{name}`"));
-
-        let mut message = if let Some(span) =
-            get_span(manifest.document(), &["lints", "cargo", lint_name], false)
-        {
-            level.title(&title).snippet(
-                Snippet::source(manifest.contents())
-                    .origin(&manifest_path)
-                    .annotation(Level::Error.span(span))
-                    .fold(true),
-            )
-        } else {
-            let lint_span = get_span(
-                ws_document,
-                &["workspace", "lints", "cargo", lint_name],
-                false,
-            )
-            .expect(&format!(
-                "could not find `cargo::{lint_name}` in `[lints]`, or `[workspace.lints]` "
-            ));
-
-            let inherited_note = if let (Some(inherit_span_key), Some(inherit_span_value)) = (
-                get_span(manifest.document(), &["lints", "workspace"], false),
-                get_span(manifest.document(), &["lints", "workspace"], true),
-            ) {
-                Level::Note.title(&second_title).snippet(
-                    Snippet::source(manifest.contents())
-                        .origin(&manifest_path)
-                        .annotation(
-                            Level::Note.span(inherit_span_key.start..inherit_span_value.end),
-                        )
-                        .fold(true),
-                )
-            } else {
-                Level::Note.title(&second_title)
+        for (name, dep) in deps.iter() {
+            let Some(dep) = dep.as_table_like() else {
+                continue;
             };
+            if !dep.contains_key("path") || dep.contains_key("version") {
+                continue;
+            }
+            for key in ["registry", "registry-index"] {
+                if !dep.contains_key(key) {
+                    continue;
+                }
+                let mut toml_path = table_path.clone();
+                toml_path.extend([name, key]);
+                let Some(span) = get_span(document, &toml_path, false) else {
+                    continue;
+                };
+                if is_allowed_by_comment(manifest.contents(), &span, &CONFLICTING_DEPENDENCY_SOURCE)
+                {
+                    continue;
+                }
+                *toml_path.last_mut().unwrap() = "path";
+                let Some(path_span) = get_span(document, &toml_path, false) else {
+                    continue;
+                };
+                if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                    *error_count += 1;
+                }
+                let title = format!("`{key}` is ignored for dependency `{name}`");
+                let mut message = level.title(&title).snippet(lint_snippet(
+                    level,
+                    manifest.contents(),
+                    &manifest_path,
+                    span,
+                    [(path_span, "`cargo` uses this source")],
+                ));
+                if emitted_source.is_none() {
+                    emitted_source = Some(format!(
+                        "`cargo::{}` is set to `{lint_level}` {reason}",
+                        CONFLICTING_DEPENDENCY_SOURCE.name
+                    ));
+                    message = message
+                        .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                        .footers(trace.iter().map(|note| Level::Note.title(note)));
+                }
+                let help = format!("remove `{key}`, or add a `version` to use when publishing");
+                message = message.footer(Level::Help.title(&help));
 
-            level
-                .title(&title)
-                .snippet(
-                    Snippet::source(ws_contents)
-                        .origin(&ws_path)
-                        .annotation(Level::Error.span(lint_span))
-                        .fold(true),
-                )
-                .footer(inherited_note)
-        };
+                gctx.shell().print_message(message)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+const DEEP_PATH_DEPENDENCY: Lint = Lint {
+    name: "deep_path_dependency",
+    desc: "path dependency reaches far outside of the package",
+    groups: &[NURSERY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for path dependencies that go up more than 3 parent directories from
+the package
+
+The number of parent directories allowed can be changed with `max-depth`, like
+`deep_path_dependency = { level = "warn", max-depth = 5 }`.
+
+### Why it is bad
+The package only builds when it is checked out next to its dependencies in
+exactly the right layout, which breaks as soon as either is moved.
+
+### Example
+```toml
+[dependencies]
+bar = { path = "../../../../bar" }
+```
+
+Consider moving the packages into a workspace together, or depending on `bar`
+from a registry or git repository.
+"#,
+    ),
+};
+
+/// The number of parent directories `deep_path_dependency` allows by default
+const DEFAULT_MAX_PATH_DEPENDENCY_DEPTH: u64 = 3;
+
+pub fn check_deep_path_dependency(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = DEEP_PATH_DEPENDENCY.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    let max_depth = DEEP_PATH_DEPENDENCY.integer_option(
+        pkg_lints,
+        "max-depth",
+        DEFAULT_MAX_PATH_DEPENDENCY_DEPTH,
+    )?;
 
+    let document = manifest.document();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (name, dep_path, span) in path_dependencies(document) {
+        let Some(depth) = parent_dir_depth(Path::new(dep_path)).filter(|depth| max_depth < *depth)
+        else {
+            continue;
+        };
+        if is_allowed_by_comment(manifest.contents(), &span, &DEEP_PATH_DEPENDENCY) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("path dependency `{name}` is outside of the package");
+        let mut message = level.title(&title).snippet(lint_snippet(
+            level,
+            manifest.contents(),
+            &manifest_path,
+            span,
+            [],
+        ));
         if emitted_source.is_none() {
             emitted_source = Some(format!(
                 "`cargo::{}` is set to `{lint_level}` {reason}",
-                UNKNOWN_LINTS.name
+                DEEP_PATH_DEPENDENCY.name
             ));
-            message = message.footer(Level::Note.title(emitted_source.as_ref().unwrap()));
-        }
-
-        if let Some(help) = help.as_ref() {
-            message = message.footer(Level::Help.title(help));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
         }
+        let note = format!(
+            "the path goes up {depth} parent directories, more than the {max_depth} allowed"
+        );
+        message = message.footer(Level::Note.title(&note));
 
         gctx.shell().print_message(message)?;
     }
-
     Ok(())
 }
 
-const UNUSED_OPTIONAL_DEPENDENCY: Lint = Lint {
-    name: "unused_optional_dependency",
-    desc: "unused optional dependency",
-    groups: &[],
-    default_level: LintLevel::Warn,
-    edition_lint_opts: None,
+/// Returns every dependency in `document` that sets a `path`, as the name of
+/// the dependency, its `path`, and the span of the `path` value
+fn path_dependencies(document: &ImDocument<String>) -> Vec<(&str, &str, Range<usize>)> {
+    let mut path_deps = Vec::new();
+    for (table_path, deps) in dependency_tables(document) {
+        for (name, dep) in deps.iter() {
+            let Some(dep_path) = dep
+                .as_table_like()
+                .and_then(|dep| dep.get("path"))
+                .and_then(|p| p.as_str())
+            else {
+                continue;
+            };
+            let mut toml_path = table_path.clone();
+            toml_path.extend([name, "path"]);
+            let Some(span) = get_span(document, &toml_path, true) else {
+                continue;
+            };
+            path_deps.push((name, dep_path, span));
+        }
+    }
+    path_deps
+}
+
+/// The number of parent directories the relative `path` goes up, once any
+/// components that cancel out are removed
+///
+/// Returns `None` for absolute paths.
+fn parent_dir_depth(path: &Path) -> Option<u64> {
+    let mut depth = 0;
+    let mut descended = 0;
+    for component in path.components() {
+        match component {
+            Component::Prefix(_) | Component::RootDir => return None,
+            Component::CurDir => {}
+            Component::ParentDir if descended == 0 => depth += 1,
+            Component::ParentDir => descended -= 1,
+            Component::Normal(_) => descended += 1,
+        }
+    }
+    Some(depth)
+}
+
+const DEPENDENCY_PATH_OUTSIDE_WORKSPACE: Lint = Lint {
+    name: "dependency_path_outside_workspace",
+    desc: "path dependency is outside of the workspace",
+    groups: &[RESTRICTION],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
     feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
     docs: Some(
         r#"
 ### What it does
-Checks for optional dependencies that are not activated by any feature
+Checks for path dependencies that resolve to a directory outside of the
+workspace root
 
 ### Why it is bad
-Starting in the 2024 edition, `cargo` no longer implicitly creates features
-for optional dependencies (see [RFC #3491]). This means that any optional
-dependency not specified with `"dep:<name>"` in some feature is now unused.
-This change may be surprising to users who have been using the implicit
-features `cargo` has been creating for optional dependencies.
+The dependency is outside of the workspace's version control, so a checkout of
+the workspace can't be built on its own. The workspace's `Cargo.lock` doesn't
+pin it either, as it only records the version of a path dependency, not its
+contents, so the build changes whenever the copy next to the workspace does.
 
 ### Example
 ```toml
-edition = "2024"
-
-[dependencies]
-bar = { version = "0.1.0", optional = true }
-
-[features]
-# No explicit feature activation for `bar`
-```
-
-Instead, the dependency should be removed or activated in a feature:
-```toml
-edition = "2024"
-
 [dependencies]
-bar = { version = "0.1.0", optional = true }
-
-[features]
-bar = ["dep:bar"]
+bar = { path = "../../bar", version = "0.1.0" }
 ```
-
-[RFC #3491]: https://rust-lang.github.io/rfcs/3491-remove-implicit-features.html
+where `../..` is above the workspace root.
 "#,
     ),
 };
 
-pub fn unused_dependencies(
+pub fn check_dependency_path_outside_workspace(
+    ws: &Workspace<'_>,
     pkg: &Package,
     path: &Path,
     pkg_lints: &TomlToolLints,
@@ -785,102 +1736,5565 @@ pub fn unused_dependencies(
     gctx: &GlobalContext,
 ) -> CargoResult<()> {
     let manifest = pkg.manifest();
-    let edition = manifest.edition();
-    // Unused optional dependencies can only exist on edition 2024+
-    if edition < Edition::Edition2024 {
+    let (lint_level, reason, trace) = DEPENDENCY_PATH_OUTSIDE_WORKSPACE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
         return Ok(());
-    }
+    };
 
-    let (lint_level, reason) =
-        UNUSED_OPTIONAL_DEPENDENCY.level(pkg_lints, edition, manifest.unstable_features());
-    if lint_level == LintLevel::Allow {
-        return Ok(());
-    }
+    let document = manifest.document();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
     let mut emitted_source = None;
-    let original_toml = manifest.original_toml();
-    // Unused dependencies were stripped from the manifest, leaving only the used ones
-    let used_dependencies = manifest
-        .dependencies()
-        .into_iter()
-        .map(|d| d.name_in_toml().to_string())
-        .collect::<HashSet<String>>();
-    let mut orig_deps = vec![
-        (
-            original_toml.dependencies.as_ref(),
-            vec![DepKind::Normal.kind_table()],
-        ),
-        (
-            original_toml.dev_dependencies.as_ref(),
-            vec![DepKind::Development.kind_table()],
-        ),
-        (
-            original_toml.build_dependencies.as_ref(),
-            vec![DepKind::Build.kind_table()],
-        ),
-    ];
-    for (name, platform) in original_toml.target.iter().flatten() {
-        orig_deps.push((
-            platform.dependencies.as_ref(),
-            vec!["target", name, DepKind::Normal.kind_table()],
-        ));
-        orig_deps.push((
-            platform.dev_dependencies.as_ref(),
-            vec!["target", name, DepKind::Development.kind_table()],
+    for (name, dep_path, span) in path_dependencies(document) {
+        if paths::normalize_path(&pkg.root().join(dep_path)).starts_with(ws.root()) {
+            continue;
+        }
+        if is_allowed_by_comment(
+            manifest.contents(),
+            &span,
+            &DEPENDENCY_PATH_OUTSIDE_WORKSPACE,
+        ) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("path dependency `{name}` is outside of the workspace");
+        let mut message = level.title(&title).snippet(lint_snippet(
+            level,
+            manifest.contents(),
+            &manifest_path,
+            span,
+            [],
         ));
-        orig_deps.push((
-            platform.build_dependencies.as_ref(),
-            vec!["target", name, DepKind::Normal.kind_table()],
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                DEPENDENCY_PATH_OUTSIDE_WORKSPACE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(Level::Note.title(
+            "it is not under the workspace's version control, and `Cargo.lock` doesn't pin its contents",
         ));
+
+        gctx.shell().print_message(message)?;
     }
-    for (deps, toml_path) in orig_deps {
-        if let Some(deps) = deps {
-            for name in deps.keys() {
-                if !used_dependencies.contains(name.as_str()) {
-                    if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
-                        *error_count += 1;
-                    }
-                    let toml_path = toml_path
-                        .iter()
-                        .map(|s| *s)
-                        .chain(std::iter::once(name.as_str()))
-                        .collect::<Vec<_>>();
-                    let level = lint_level.to_diagnostic_level();
-                    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    Ok(())
+}
 
-                    let mut message = level.title(UNUSED_OPTIONAL_DEPENDENCY.desc).snippet(
-                        Snippet::source(manifest.contents())
-                            .origin(&manifest_path)
-                            .annotation(level.span(
-                                get_span(manifest.document(), toml_path.as_slice(), false).unwrap(),
-                            ))
-                            .fold(true),
-                    );
-                    if emitted_source.is_none() {
-                        emitted_source = Some(format!(
-                            "`cargo::{}` is set to `{lint_level}` {reason}",
-                            UNUSED_OPTIONAL_DEPENDENCY.name
-                        ));
-                        message =
-                            message.footer(Level::Note.title(emitted_source.as_ref().unwrap()));
-                    }
-                    let help = format!(
-                        "remove the dependency or activate it in a feature with `dep:{name}`"
-                    );
-                    message = message.footer(Level::Help.title(&help));
+const DEPENDENCY_REGISTRY_NOT_CONFIGURED: Lint = Lint {
+    name: "dependency_registry_not_configured",
+    desc: "dependency from a registry that is not configured",
+    groups: &[CORRECTNESS],
+    default_level: LintLevel::Deny,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for `[workspace.dependencies]` with a `registry` that has no
+`[registries]` entry in the cargo config
 
-                    gctx.shell().print_message(message)?;
-                }
-            }
+### Why it is bad
+The registry's index cannot be found, so every member that inherits the
+dependency fails to load. Until one does, the mistake goes unnoticed.
+
+Dependencies of a package are not checked, as an unconfigured registry is
+already an error when the package is loaded.
+
+### Example
+```toml
+[workspace.dependencies]
+foo = { version = "0.1.0", registry = "my-registry" }
+```
+
+Should be accompanied by an entry in `.cargo/config.toml`:
+```toml
+[registries.my-registry]
+index = "https://my-intranet:8080/git/index"
+```
+"#,
+    ),
+};
+
+pub fn check_dependency_registry_not_configured(
+    ws: &Workspace<'_>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let (contents, document, resolved_ws, unstable_features) = match ws.root_maybe() {
+        MaybePackage::Package(pkg) => {
+            let manifest = pkg.manifest();
+            (
+                manifest.contents(),
+                manifest.document(),
+                manifest.resolved_toml().workspace.as_ref(),
+                manifest.unstable_features(),
+            )
+        }
+        MaybePackage::Virtual(vm) => (
+            vm.contents(),
+            vm.document(),
+            vm.resolved_toml().workspace.as_ref(),
+            vm.unstable_features(),
+        ),
+    };
+    let Some(resolved_ws) = resolved_ws else {
+        return Ok(());
+    };
+    let ws_lints = resolved_ws
+        .lints
+        .as_ref()
+        .and_then(|lints| lints.get("cargo"))
+        .cloned()
+        .unwrap_or_default();
+    let (lint_level, reason, trace) = DEPENDENCY_REGISTRY_NOT_CONFIGURED.level_with_trace(
+        &ws_lints,
+        Edition::default(),
+        unstable_features,
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let Some(ws_deps) = document
+        .as_item()
+        .get("workspace")
+        .and_then(|ws| ws.get("dependencies"))
+        .and_then(|d| d.as_table_like())
+    else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(ws.root_manifest(), gctx);
+    let config_path = rel_cwd_manifest_path(&ws.root().join(".cargo").join("config.toml"), gctx);
+    let mut emitted_source = None;
+    for (name, dep) in ws_deps.iter() {
+        let Some(registry) = dep
+            .as_table_like()
+            .and_then(|dep| dep.get("registry"))
+            .and_then(|r| r.as_str())
+        else {
+            continue;
+        };
+        if registry == CRATES_IO_REGISTRY
+            || gctx
+                .get_string(&format!("registries.{registry}.index"))
+                .is_ok_and(|index| index.is_some())
+        {
+            continue;
+        }
+        let Some(span) = get_span(
+            document,
+            &["workspace", "dependencies", name, "registry"],
+            true,
+        ) else {
+            continue;
+        };
+        if is_allowed_by_comment(contents, &span, &DEPENDENCY_REGISTRY_NOT_CONFIGURED) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("registry `{registry}` of dependency `{name}` is not configured");
+        let mut message = level.title(&title).snippet(
+            Snippet::source(contents)
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                DEPENDENCY_REGISTRY_NOT_CONFIGURED.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
         }
+        let help = format!(
+            "set the registry's index in `[registries.{registry}]` of a cargo config file, \
+             like `{config_path}`"
+        );
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
     }
     Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use itertools::Itertools;
-    use snapbox::ToDebug;
-    use std::collections::HashSet;
+const DEPRECATED_MANIFEST_KEY: Lint = Lint {
+    name: "deprecated_manifest_key",
+    desc: "manifest key is deprecated",
+    groups: &[STYLE],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for manifest keys that have been renamed, like `[project]`
+
+### Why it is bad
+The old name is only kept for backwards compatibility, and may be rejected in
+newer editions. `[project]`, for example, is an error as of the 2024 edition.
+
+### Example
+```toml
+[project]
+name = "foo"
+version = "0.1.0"
+```
+
+Should be written as:
+```toml
+[package]
+name = "foo"
+version = "0.1.0"
+```
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+/// Top-level manifest keys that were renamed, as `(old, new)`
+const DEPRECATED_MANIFEST_KEYS: &[(&str, &str)] = &[("project", "package")];
+
+pub fn check_deprecated_manifest_key(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = DEPRECATED_MANIFEST_KEY.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (old, new, span) in deprecated_manifest_keys(manifest.contents(), manifest.document()) {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("`{old}` is deprecated in favor of `{new}`");
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &DEPRECATED_MANIFEST_KEY,
+                lint_level,
+                path,
+                span,
+                &title,
+            );
+            continue;
+        }
+        let mut message = level.title(&title).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                DEPRECATED_MANIFEST_KEY.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("rename `{old}` to `{new}`");
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Renames the keys reported by `deprecated_manifest_key` in `contents`, if
+/// the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of keys that were renamed.
+pub fn fix_deprecated_manifest_key(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) =
+        DEPRECATED_MANIFEST_KEY.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+
+    let document: ImDocument<String> = ImDocument::parse(contents.to_owned())?;
+    let deprecated = deprecated_manifest_keys(contents, &document);
+    if deprecated.is_empty() {
+        return Ok(None);
+    }
+    let mut document = document.into_mut();
+    let root = document.as_table_mut();
+    let mut fixes = 0;
+    for (old, new, _) in deprecated {
+        let Some(old_key) = root.key(old).cloned() else {
+            continue;
+        };
+        let Some(item) = root.remove(old) else {
+            continue;
+        };
+        root.insert(new, item);
+        let mut new_key = root.key_mut(new).expect("just inserted");
+        *new_key.dotted_decor_mut() = old_key.dotted_decor().clone();
+        *new_key.leaf_decor_mut() = old_key.leaf_decor().clone();
+        fixes += 1;
+    }
+    Ok(Some((document.to_string(), fixes)))
+}
+
+/// Finds every deprecated top-level key, as its replacement and the span of
+/// the key, skipping any that are allowed by a `# cargo::allow` comment
+///
+/// Keys whose replacement is also present are skipped, as they cannot simply
+/// be renamed.
+fn deprecated_manifest_keys(
+    contents: &str,
+    document: &ImDocument<String>,
+) -> Vec<(&'static str, &'static str, Range<usize>)> {
+    let Some(root) = document.as_item().as_table_like() else {
+        return Vec::new();
+    };
+    DEPRECATED_MANIFEST_KEYS
+        .iter()
+        .filter(|(old, new)| root.contains_key(old) && !root.contains_key(new))
+        .filter_map(|&(old, new)| Some((old, new, get_span(document, &[old], false)?)))
+        .filter(|(_, _, span)| !is_allowed_by_comment(contents, span, &DEPRECATED_MANIFEST_KEY))
+        .collect()
+}
+
+const DUPLICATE_FEATURE_VALUE: Lint = Lint {
+    name: "duplicate_feature_value",
+    desc: "feature lists the same value more than once",
+    groups: &[COMPLEXITY],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for features that list the same feature, dependency, or dependency
+feature more than once
+
+Values are compared by what they activate, so `"bar/std"` and `"bar?/std"`, or
+`"bar"` and `"dep:bar"`, are not duplicates of each other.
+
+### Why it is bad
+Activating something twice has no effect, so the repeated entry is only noise.
+
+### Example
+```toml
+[features]
+foo = ["bar", "bar"]
+bar = []
+```
+
+Should be written as:
+```toml
+[features]
+foo = ["bar"]
+bar = []
+```
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+pub fn check_duplicate_feature_value(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = DUPLICATE_FEATURE_VALUE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for duplicate in duplicate_feature_values(manifest.contents(), manifest.document()) {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!(
+            "feature `{}` lists `{}` more than once",
+            duplicate.feature, duplicate.value
+        );
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &DUPLICATE_FEATURE_VALUE,
+                lint_level,
+                path,
+                duplicate.span,
+                &title,
+            );
+            continue;
+        }
+        let mut message = level.title(&title).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(duplicate.span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                DUPLICATE_FEATURE_VALUE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("remove the duplicate `\"{}\"`", duplicate.value);
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Removes the entries reported by `duplicate_feature_value` from `contents`,
+/// if the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of entries that were removed.
+pub fn fix_duplicate_feature_value(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) =
+        DUPLICATE_FEATURE_VALUE.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+
+    let document: ImDocument<String> = ImDocument::parse(contents.to_owned())?;
+    let duplicates = duplicate_feature_values(contents, &document);
+    if duplicates.is_empty() {
+        return Ok(None);
+    }
+    let mut document = document.into_mut();
+    let Some(features) = document
+        .as_table_mut()
+        .get_mut("features")
+        .and_then(|item| item.as_table_like_mut())
+    else {
+        return Ok(None);
+    };
+    let mut fixes = 0;
+    // Remove from the back so the indices of the remaining duplicates stay
+    // valid. A duplicate is never the first entry, so the whitespace before
+    // the entry can go with it.
+    for duplicate in duplicates.iter().rev() {
+        let Some(values) = features
+            .get_mut(&duplicate.feature)
+            .and_then(|item| item.as_array_mut())
+        else {
+            continue;
+        };
+        values.remove(duplicate.index);
+        fixes += 1;
+    }
+    Ok(Some((document.to_string(), fixes)))
+}
+
+/// An entry of a feature that repeats an earlier entry of the same feature
+struct DuplicateFeatureValue {
+    feature: String,
+    value: String,
+    /// The position of the entry in the feature's array
+    index: usize,
+    span: Range<usize>,
+}
+
+/// Finds every entry of a feature that activates the same thing as an earlier
+/// entry, skipping any that are allowed by a `# cargo::allow` comment
+fn duplicate_feature_values(
+    contents: &str,
+    document: &ImDocument<String>,
+) -> Vec<DuplicateFeatureValue> {
+    let mut duplicates = Vec::new();
+    let Some(features) = document
+        .as_table()
+        .get("features")
+        .and_then(|f| f.as_table_like())
+    else {
+        return duplicates;
+    };
+    for (feature, values) in features.iter() {
+        let Some(values) = values.as_array() else {
+            continue;
+        };
+        let mut seen = HashSet::new();
+        for (index, value) in values.iter().enumerate() {
+            let Some(s) = value.as_str() else {
+                continue;
+            };
+            if seen.insert(FeatureValue::new(InternedString::new(s))) {
+                continue;
+            }
+            let Some(span) = value.span() else {
+                continue;
+            };
+            if is_allowed_by_comment(contents, &span, &DUPLICATE_FEATURE_VALUE) {
+                continue;
+            }
+            duplicates.push(DuplicateFeatureValue {
+                feature: feature.to_owned(),
+                value: s.to_owned(),
+                index,
+                span,
+            });
+        }
+    }
+    duplicates
+}
+
+const FEATURE_ACTIVATES_DEFAULT_OF_OPTIONAL_DEP: Lint = Lint {
+    name: "feature_activates_default_of_optional_dep",
+    desc: "feature implicitly activates an optional dependency",
+    groups: &[PEDANTIC],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for feature values that name an optional dependency without the
+`dep:` prefix
+
+### Why it is bad
+Before the 2024 edition, every optional dependency that is not referenced with
+`dep:<name>` gets an implicit feature of the same name. A feature value like
+`"bar"` activates that implicit feature, which in turn activates the optional
+dependency. Reading the manifest, it is not clear whether `"bar"` refers to a
+feature or a dependency.
+
+### Example
+```toml
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["bar"]
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["dep:bar"]
+```
+
+Note that once any feature uses `dep:bar`, the implicit `bar` feature is no
+longer created. Add `bar = ["dep:bar"]` if it should remain a public feature.
+"#,
+    ),
+};
+
+pub fn check_feature_activates_default_of_optional_dep(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let edition = manifest.edition();
+    // In Edition 2024+, optional dependencies no longer create implicit features,
+    // so a value without `dep:` can only refer to a feature
+    if edition >= Edition::Edition2024 {
+        return Ok(());
+    }
+
+    let (lint_level, reason, trace) = FEATURE_ACTIVATES_DEFAULT_OF_OPTIONAL_DEP.level_with_trace(
+        pkg_lints,
+        edition,
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let Some(features) = manifest.resolved_toml().features() else {
+        return Ok(());
+    };
+    let activated_opt_deps = activated_opt_deps(manifest);
+    // Only optional dependencies that are neither referenced with `dep:` nor
+    // shadowed by an explicit feature have an implicit feature
+    let implicit_features = manifest
+        .dependencies()
+        .iter()
+        .filter(|dep| dep.is_optional())
+        .map(|dep| dep.name_in_toml().as_str())
+        .filter(|name| !activated_opt_deps.contains(name) && !features.contains_key(*name))
+        .collect::<HashSet<_>>();
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (feature, values) in features {
+        for value in values {
+            let FeatureValue::Feature(dep_name) = FeatureValue::new(InternedString::new(value))
+            else {
+                continue;
+            };
+            if !implicit_features.contains(dep_name.as_str()) {
+                continue;
+            }
+            let Some(span) = get_span(
+                manifest.document(),
+                &["features", feature.as_str(), value.as_str()],
+                false,
+            ) else {
+                continue;
+            };
+            if is_allowed_by_comment(
+                manifest.contents(),
+                &span,
+                &FEATURE_ACTIVATES_DEFAULT_OF_OPTIONAL_DEP,
+            ) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let title = format!(
+                "feature `{feature}` implicitly activates the optional dependency `{dep_name}`"
+            );
+            let mut message = level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    FEATURE_ACTIVATES_DEFAULT_OF_OPTIONAL_DEP.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            let note = format!(
+                "`{dep_name}` is the implicit feature created for the optional dependency `{dep_name}`"
+            );
+            let help = format!("activate the dependency explicitly with `\"dep:{dep_name}\"`");
+            message = message
+                .footer(Level::Note.title(&note))
+                .footer(Level::Help.title(&help));
+
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+const FEATURE_BODY_REFERENCES_REMOVED_DEP: Lint = Lint {
+    name: "feature_body_references_removed_dep",
+    desc: "feature references a dependency that is not declared",
+    groups: &[CORRECTNESS],
+    default_level: LintLevel::Deny,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for `dep:name`, `name/feature`, and `name?/feature` values in
+`[features]` where `name` is not a dependency of the package, like after the
+dependency was removed
+
+While `-Zcargo-lints` is enabled, such values are ignored when loading the
+manifest and reported by this lint, rather than failing to load it.
+
+### Why it is bad
+The feature refers to a dependency that doesn't exist, so it can't be
+resolved.
+
+### Example
+```toml
+[dependencies]
+
+[features]
+serde = ["dep:serde", "bar?/serde"]
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+serde = { version = "1.0.0", optional = true }
+
+[features]
+serde = ["dep:serde", "bar?/serde"]
+```
+"#,
+    ),
+};
+
+pub fn check_feature_body_references_removed_dep(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = FEATURE_BODY_REFERENCES_REMOVED_DEP.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let document = manifest.document();
+    let Some(features) = document
+        .as_table()
+        .get("features")
+        .and_then(|f| f.as_table_like())
+    else {
+        return Ok(());
+    };
+    let dep_names: HashSet<_> = manifest
+        .dependencies()
+        .iter()
+        .map(|dep| dep.name_in_toml())
+        .collect();
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (feature, values) in features.iter() {
+        let Some(values) = values.as_array() else {
+            continue;
+        };
+        for value in values.iter() {
+            let Some(s) = value.as_str() else {
+                continue;
+            };
+            let Some(dep_name) = dangling_dep_reference(InternedString::new(s), &dep_names) else {
+                continue;
+            };
+            let Some(span) = value.span() else {
+                continue;
+            };
+            if is_allowed_by_comment(
+                manifest.contents(),
+                &span,
+                &FEATURE_BODY_REFERENCES_REMOVED_DEP,
+            ) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let title =
+                format!("feature `{feature}` includes `{s}`, but `{dep_name}` is not a dependency");
+            let mut message = level.title(&title).snippet(lint_snippet(
+                level,
+                manifest.contents(),
+                &manifest_path,
+                span,
+                [],
+            ));
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    FEATURE_BODY_REFERENCES_REMOVED_DEP.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            let help = format!("remove `\"{s}\"`, or declare `{dep_name}` as a dependency");
+            message = message.footer(Level::Help.title(&help));
+
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+/// The dependency `value` refers to with `dep:name`, `name/feature`, or
+/// `name?/feature`, if it is not in `dep_names`
+///
+/// Values combining `dep:` with `/` are malformed rather than dangling, and are
+/// left to manifest validation.
+pub(crate) fn dangling_dep_reference(
+    value: InternedString,
+    dep_names: &HashSet<InternedString>,
+) -> Option<InternedString> {
+    match FeatureValue::new(value) {
+        Dep { dep_name } | DepFeature { dep_name, .. }
+            if !dep_name.starts_with("dep:") && !dep_names.contains(&dep_name) =>
+        {
+            Some(dep_name)
+        }
+        _ => None,
+    }
+}
+
+const FEATURE_ENABLES_OPTIONAL_DEP_WITHOUT_QUESTION_MARK: Lint = Lint {
+    name: "feature_enables_optional_dep_without_question_mark",
+    desc: "feature of an optional dependency also activates the dependency",
+    groups: &[PEDANTIC],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MaybeIncorrect,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for feature values like `"bar/std"` where `bar` is an optional
+dependency
+
+Values are not reported if the same feature also activates the dependency with
+`"dep:bar"`.
+
+### Why it is bad
+Besides enabling the `std` feature of `bar`, `"bar/std"` activates `bar`
+itself. This is easy to miss when the intent was only to enable `std` in case
+`bar` is used, which is what `"bar?/std"` does.
+
+### Example
+```toml
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+std = ["bar/std"]
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+std = ["bar?/std"]
+```
+"#,
+    ),
+};
+
+pub fn check_feature_enables_optional_dep_without_question_mark(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = FEATURE_ENABLES_OPTIONAL_DEP_WITHOUT_QUESTION_MARK
+        .level_with_trace(
+            pkg_lints,
+            manifest.edition(),
+            manifest.unstable_features(),
+            gctx,
+        );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let Some(features) = manifest.resolved_toml().features() else {
+        return Ok(());
+    };
+    let optional_deps = manifest
+        .dependencies()
+        .iter()
+        .filter(|dep| dep.is_optional())
+        .map(|dep| dep.name_in_toml())
+        .collect::<HashSet<_>>();
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (feature, values) in features {
+        let values = values
+            .iter()
+            .map(|value| (value, FeatureValue::new(InternedString::new(value))))
+            .collect::<Vec<_>>();
+        for (value, feature_value) in &values {
+            let DepFeature {
+                dep_name,
+                dep_feature,
+                weak: false,
+            } = feature_value
+            else {
+                continue;
+            };
+            if !optional_deps.contains(dep_name) {
+                continue;
+            }
+            // Activating the dependency is clearly intended
+            if values
+                .iter()
+                .any(|(_, v)| matches!(v, Dep { dep_name: d } if d == dep_name))
+            {
+                continue;
+            }
+            let Some(span) = get_span(
+                manifest.document(),
+                &["features", feature.as_str(), value.as_str()],
+                false,
+            ) else {
+                continue;
+            };
+            if is_allowed_by_comment(
+                manifest.contents(),
+                &span,
+                &FEATURE_ENABLES_OPTIONAL_DEP_WITHOUT_QUESTION_MARK,
+            ) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let title = format!(
+                "feature `{feature}` activates the optional dependency `{dep_name}` through `{value}`"
+            );
+            let mut message = level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    FEATURE_ENABLES_OPTIONAL_DEP_WITHOUT_QUESTION_MARK.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            let note = format!("`{value}` enables `{dep_feature}` and also activates `{dep_name}`");
+            let help = format!(
+                "if `{dep_name}` should not be activated, use `\"{dep_name}?/{dep_feature}\"`"
+            );
+            message = message
+                .footer(Level::Note.title(&note))
+                .footer(Level::Help.title(&help));
+
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+/// This lint is only to be used for testing purposes
+const IM_A_TEAPOT: Lint = Lint {
+    name: "im_a_teapot",
+    desc: "`im_a_teapot` is specified",
+    groups: &[TEST_DUMMY_UNSTABLE],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: Some(Feature::test_dummy_unstable()),
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.78",
+    docs: None,
+};
+
+pub fn check_im_a_teapot(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    report: &mut Vec<String>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = IM_A_TEAPOT.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    if manifest
+        .resolved_toml()
+        .package()
+        .is_some_and(|p| p.im_a_teapot.is_some())
+    {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let manifest_path = rel_cwd_manifest_path(path, gctx);
+        let emitted_reason = format!(
+            "`cargo::{}` is set to `{lint_level}` {reason}",
+            IM_A_TEAPOT.name
+        );
+
+        let key_span = get_span(manifest.document(), &["package", "im-a-teapot"], false).unwrap();
+        let value_span = get_span(manifest.document(), &["package", "im-a-teapot"], true).unwrap();
+        let message = level
+            .title(IM_A_TEAPOT.desc)
+            .snippet(lint_snippet(
+                level,
+                manifest.contents(),
+                &manifest_path,
+                key_span.start..value_span.end,
+                [],
+            ))
+            .footer(Level::Note.title(&emitted_reason))
+            .footers(trace.iter().map(|note| Level::Note.title(note)));
+
+        report.push(gctx.shell().render_message(message));
+    }
+    Ok(())
+}
+
+const IMPLICIT_FEATURES: Lint = Lint {
+    name: "implicit_features",
+    desc: "implicit features for optional dependencies is deprecated and will be unavailable in the 2024 edition",
+    groups: &[],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.79",
+    docs: Some(r#"
+### What it does
+Checks for implicit features for optional dependencies
+
+### Why it is bad
+By default, cargo will treat any optional dependency as a [feature]. As of
+cargo 1.60, these can be disabled by declaring a feature that activates the
+optional dependency as `dep:<name>` (see [RFC #3143]).
+
+In the 2024 edition, `cargo` will stop exposing optional dependencies as
+features implicitly, requiring users to add `foo = ["dep:foo"]` if they
+still want it exposed.
+
+For more information, see [RFC #3491]
+
+### Example
+```toml
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+# No explicit feature activation for `bar`
+```
+
+Instead, the dependency should have an explicit feature:
+```toml
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+bar = ["dep:bar"]
+```
+
+[feature]: https://doc.rust-lang.org/cargo/reference/features.html
+[RFC #3143]: https://rust-lang.github.io/rfcs/3143-cargo-weak-namespaced-features.html
+[RFC #3491]: https://rust-lang.github.io/rfcs/3491-remove-implicit-features.html
+"#
+    ),
+};
+
+pub fn check_implicit_features(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let edition = manifest.edition();
+    // In Edition 2024+, instead of creating optional features, the dependencies are unused.
+    // See `UNUSED_OPTIONAL_DEPENDENCY`
+    if edition >= Edition::Edition2024 {
+        return Ok(());
+    }
+
+    let (lint_level, reason, trace) =
+        IMPLICIT_FEATURES.level_with_trace(pkg_lints, edition, manifest.unstable_features(), gctx);
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let activated_opt_deps = activated_opt_deps(manifest);
+
+    let mut emitted_source = None;
+    for dep in manifest.dependencies() {
+        let dep_name_in_toml = dep.name_in_toml();
+        if !dep.is_optional() || activated_opt_deps.contains(dep_name_in_toml.as_str()) {
+            continue;
+        }
+        let mut toml_path = vec![dep.kind().kind_table(), dep_name_in_toml.as_str()];
+        let platform = dep.platform().map(|p| p.to_string());
+        if let Some(platform) = platform.as_ref() {
+            toml_path.insert(0, platform);
+            toml_path.insert(0, "target");
+        }
+        let span = get_span(manifest.document(), &toml_path, false).unwrap();
+        if is_allowed_by_comment(manifest.contents(), &span, &IMPLICIT_FEATURES) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let manifest_path = rel_cwd_manifest_path(path, gctx);
+        let mut message = level.title(IMPLICIT_FEATURES.desc).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                IMPLICIT_FEATURES.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+const MANIFEST_BYTE_ORDER_MARK: Lint = Lint {
+    name: "manifest_byte_order_mark",
+    desc: "manifest starts with a byte order mark",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for a UTF-8 byte order mark (BOM) at the start of the manifest
+
+### Why it is bad
+Cargo ignores the byte order mark, but other tools that read `Cargo.toml` may
+not, and fail to parse it.
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+const BYTE_ORDER_MARK: &str = "\u{feff}";
+
+pub fn check_manifest_byte_order_mark(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = MANIFEST_BYTE_ORDER_MARK.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    if !manifest.contents().starts_with(BYTE_ORDER_MARK) {
+        return Ok(());
+    }
+
+    if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+        *error_count += 1;
+    }
+    let span = 0..BYTE_ORDER_MARK.len();
+    if let MessageFormat::Json { .. } = message_format {
+        print_json(
+            gctx,
+            &MANIFEST_BYTE_ORDER_MARK,
+            lint_level,
+            path,
+            span,
+            MANIFEST_BYTE_ORDER_MARK.desc,
+        );
+        return Ok(());
+    }
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let emitted_reason = format!(
+        "`cargo::{}` is set to `{lint_level}` {reason}",
+        MANIFEST_BYTE_ORDER_MARK.name
+    );
+    let message = level
+        .title(MANIFEST_BYTE_ORDER_MARK.desc)
+        .snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        )
+        .footer(Level::Note.title(&emitted_reason))
+        .footers(trace.iter().map(|note| Level::Note.title(note)))
+        .footer(Level::Help.title("remove the byte order mark"));
+
+    gctx.shell().print_message(message)?;
+    Ok(())
+}
+
+/// Removes the byte order mark reported by `manifest_byte_order_mark` from
+/// `contents`, if the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of changes, which is always one.
+pub fn fix_manifest_byte_order_mark(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) =
+        MANIFEST_BYTE_ORDER_MARK.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+    Ok(contents
+        .strip_prefix(BYTE_ORDER_MARK)
+        .map(|fixed| (fixed.to_owned(), 1)))
+}
+
+const MANIFEST_INCONSISTENT_INDENTATION: Lint = Lint {
+    name: "manifest_inconsistent_indentation",
+    desc: "manifest line is not indented by a multiple of the indentation width",
+    groups: &[STYLE],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for lines in the manifest that are indented by a number of spaces that
+is not a multiple of 4, reporting the first such line in each table
+
+The indentation width can be changed with `indent-width`, like
+`manifest_inconsistent_indentation = { level = "warn", indent-width = 2 }`.
+Lines indented with tabs are left to `tab_indentation`.
+
+### Why it is bad
+Mixing indentation widths makes the manifest harder to read.
+
+### Example
+```toml
+[dependencies]
+bar = { version = "0.1.0", features = [
+    "std",
+   "alloc",
+] }
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0", features = [
+    "std",
+    "alloc",
+] }
+```
+"#,
+    ),
+};
+
+/// The number of spaces `manifest_inconsistent_indentation` expects
+/// indentation to be a multiple of by default
+const DEFAULT_INDENT_WIDTH: u64 = 4;
+
+pub fn check_manifest_inconsistent_indentation(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = MANIFEST_INCONSISTENT_INDENTATION.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    let indent_width = MANIFEST_INCONSISTENT_INDENTATION.integer_option(
+        pkg_lints,
+        "indent-width",
+        DEFAULT_INDENT_WIDTH,
+    )?;
+    if indent_width == 0 {
+        anyhow::bail!(
+            "`indent-width` for `cargo::{}` must be greater than 0",
+            MANIFEST_INCONSISTENT_INDENTATION.name
+        );
+    }
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for span in
+        inconsistent_indentation_spans(manifest.contents(), manifest.document(), indent_width)
+    {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!(
+            "line is indented by {} spaces, which is not a multiple of {indent_width}",
+            span.len()
+        );
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &MANIFEST_INCONSISTENT_INDENTATION,
+                lint_level,
+                path,
+                span,
+                &title,
+            );
+            continue;
+        }
+        let mut message = level.title(&title).snippet(lint_snippet(
+            level,
+            manifest.contents(),
+            &manifest_path,
+            span,
+            [],
+        ));
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                MANIFEST_INCONSISTENT_INDENTATION.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("indent by a multiple of {indent_width} spaces");
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Gets the span of the indentation of the first line in each table of
+/// `contents` that is not indented by a multiple of `indent_width` spaces
+///
+/// Blank lines, lines indented with tabs, lines that are part of a multi-line
+/// string value, and lines allowed by a `# cargo::allow` comment are skipped.
+fn inconsistent_indentation_spans(
+    contents: &str,
+    document: &ImDocument<String>,
+    indent_width: u64,
+) -> Vec<Range<usize>> {
+    let strings = multiline_string_spans(document);
+    let headers = table_header_starts(document);
+
+    let mut spans = Vec::new();
+    let mut reported = false;
+    let mut line_start = 0;
+    for line in contents.split_inclusive('\n') {
+        let line_end = line_start + line.len();
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        let span = line_start..line_start + indent;
+        line_start = line_end;
+        if headers
+            .iter()
+            .any(|start| span.start <= *start && *start < line_end)
+        {
+            reported = false;
+            continue;
+        }
+        if reported
+            || indent as u64 % indent_width == 0
+            || line.trim().is_empty()
+            || line[indent..].starts_with('\t')
+            || strings
+                .iter()
+                .any(|s| s.start < span.start && span.end <= s.end)
+            || is_allowed_by_comment(contents, &span, &MANIFEST_INCONSISTENT_INDENTATION)
+        {
+            continue;
+        }
+        reported = true;
+        spans.push(span);
+    }
+    spans
+}
+
+/// Gets where the `[table]` and `[[array-of-tables]]` headers of `document`
+/// start
+fn table_header_starts(document: &ImDocument<String>) -> Vec<usize> {
+    fn headers(table: &toml_edit::Table, starts: &mut Vec<usize>) {
+        for (_, item) in table.iter() {
+            match item {
+                toml_edit::Item::Table(table) => {
+                    if !table.is_dotted() {
+                        starts.extend(table.span().map(|span| span.start));
+                    }
+                    headers(table, starts);
+                }
+                toml_edit::Item::ArrayOfTables(tables) => {
+                    for table in tables.iter() {
+                        starts.extend(table.span().map(|span| span.start));
+                        headers(table, starts);
+                    }
+                }
+                toml_edit::Item::Value(_) | toml_edit::Item::None => {}
+            }
+        }
+    }
+
+    let mut starts = Vec::new();
+    headers(document.as_table(), &mut starts);
+    starts
+}
+
+const MANIFEST_TRAILING_WHITESPACE: Lint = Lint {
+    name: "manifest_trailing_whitespace",
+    desc: "trailing whitespace in manifest",
+    groups: &[STYLE],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for lines in the manifest that end with whitespace
+
+### Why it is bad
+Trailing whitespace is invisible in most editors and adds noise to diffs.
+
+### Example
+```toml
+[package]
+name = "foo"··
+version = "0.1.0"
+```
+where `··` is trailing whitespace.
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+pub fn check_manifest_trailing_whitespace(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = MANIFEST_TRAILING_WHITESPACE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for span in trailing_whitespace_spans(manifest.contents(), manifest.document()) {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &MANIFEST_TRAILING_WHITESPACE,
+                lint_level,
+                path,
+                span,
+                MANIFEST_TRAILING_WHITESPACE.desc,
+            );
+            continue;
+        }
+        let mut message = level.title(MANIFEST_TRAILING_WHITESPACE.desc).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                MANIFEST_TRAILING_WHITESPACE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(Level::Help.title("remove the trailing whitespace"));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Removes the trailing whitespace reported by `manifest_trailing_whitespace`
+/// from `contents`, if the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of lines that were changed.
+pub fn fix_manifest_trailing_whitespace(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) =
+        MANIFEST_TRAILING_WHITESPACE.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+
+    // `contents` may have been changed since the manifest was loaded, e.g. by
+    // `cargo fix --edition`, so the spans have to be recomputed
+    let document: ImDocument<String> = ImDocument::parse(contents.to_owned())?;
+    let spans = trailing_whitespace_spans(contents, &document);
+    if spans.is_empty() {
+        return Ok(None);
+    }
+    let mut fixed = String::with_capacity(contents.len());
+    let mut last = 0;
+    for span in &spans {
+        fixed.push_str(&contents[last..span.start]);
+        last = span.end;
+    }
+    fixed.push_str(&contents[last..]);
+    Ok(Some((fixed, spans.len())))
+}
+
+/// Gets the spans of whitespace at the end of each line of `contents`, skipping
+/// whitespace that is part of a multi-line string value or allowed by a
+/// `# cargo::allow` comment
+fn trailing_whitespace_spans(contents: &str, document: &ImDocument<String>) -> Vec<Range<usize>> {
+    let strings = multiline_string_spans(document);
+
+    let mut spans = Vec::new();
+    let mut line_start = 0;
+    for line in contents.split_inclusive('\n') {
+        let content = line
+            .strip_suffix('\n')
+            .map(|l| l.strip_suffix('\r').unwrap_or(l))
+            .unwrap_or(line);
+        let trimmed = content.trim_end_matches([' ', '\t']);
+        let span = line_start + trimmed.len()..line_start + content.len();
+        line_start += line.len();
+        if span.is_empty()
+            || strings
+                .iter()
+                .any(|s| s.start < span.start && span.end <= s.end)
+            || is_allowed_by_comment(contents, &span, &MANIFEST_TRAILING_WHITESPACE)
+        {
+            continue;
+        }
+        spans.push(span);
+    }
+    spans
+}
+
+/// Gets the spans of the multi-line string values in `document`
+fn multiline_string_spans(document: &ImDocument<String>) -> Vec<Range<usize>> {
+    fn multiline_strings(item: &toml_edit::Item, spans: &mut Vec<Range<usize>>) {
+        match item {
+            toml_edit::Item::Value(value) => multiline_strings_in_value(value, spans),
+            toml_edit::Item::Table(table) => {
+                for (_, item) in table.iter() {
+                    multiline_strings(item, spans);
+                }
+            }
+            toml_edit::Item::ArrayOfTables(tables) => {
+                for table in tables.iter() {
+                    for (_, item) in table.iter() {
+                        multiline_strings(item, spans);
+                    }
+                }
+            }
+            toml_edit::Item::None => {}
+        }
+    }
+    fn multiline_strings_in_value(value: &toml_edit::Value, spans: &mut Vec<Range<usize>>) {
+        match value {
+            toml_edit::Value::String(s) if s.value().contains('\n') => {
+                spans.extend(s.span());
+            }
+            toml_edit::Value::Array(array) => {
+                for value in array.iter() {
+                    multiline_strings_in_value(value, spans);
+                }
+            }
+            toml_edit::Value::InlineTable(table) => {
+                for (_, value) in table.iter() {
+                    multiline_strings_in_value(value, spans);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut strings = Vec::new();
+    multiline_strings(document.as_item(), &mut strings);
+    strings
+}
+
+const MISPLACED_LINTS_TABLE: Lint = Lint {
+    name: "misplaced_lints_table",
+    desc: "`[lints]` in a virtual manifest has no effect",
+    groups: &[CORRECTNESS],
+    default_level: LintLevel::Deny,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for a `[lints]` table in a virtual manifest
+
+### Why it is bad
+A virtual manifest has no `[package]`, so there is nothing for a `[lints]`
+table to apply to and it is silently ignored. Lints meant to be shared across
+a workspace need to be declared in `[workspace.lints]`, and then inherited by
+each member with `lints.workspace = true`.
+
+### Example
+```toml
+[workspace]
+members = ["foo"]
+
+[lints.cargo]
+unknown_lints = "deny"
+```
+
+Instead, the table should be placed under `[workspace.lints]`:
+```toml
+[workspace]
+members = ["foo"]
+
+[workspace.lints.cargo]
+unknown_lints = "deny"
+```
+"#,
+    ),
+};
+
+pub fn check_misplaced_lints_table(
+    vm: &VirtualManifest,
+    path: &Path,
+    ws_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let (lint_level, reason, trace) = MISPLACED_LINTS_TABLE.level_with_trace(
+        ws_lints,
+        Edition::default(),
+        vm.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    if vm.original_toml().lints.is_none() {
+        return Ok(());
+    }
+    let Some(span) = get_span(vm.document(), &["lints"], false) else {
+        return Ok(());
+    };
+
+    if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+        *error_count += 1;
+    }
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let emitted_reason = format!(
+        "`cargo::{}` is set to `{lint_level}` {reason}",
+        MISPLACED_LINTS_TABLE.name
+    );
+    let message = level
+        .title(MISPLACED_LINTS_TABLE.desc)
+        .snippet(
+            Snippet::source(vm.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        )
+        .footer(Level::Note.title(&emitted_reason))
+        .footers(trace.iter().map(|note| Level::Note.title(note)))
+        .footer(Level::Help.title("move the table under `[workspace.lints]`"));
+
+    gctx.shell().print_message(message)?;
+    Ok(())
+}
+
+const OVERLY_BROAD_DEPENDENCY: Lint = Lint {
+    name: "overly_broad_dependency",
+    desc: "dependency version requirement matches nearly every version",
+    groups: &[PEDANTIC],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for dependency version requirements without an upper bound, like
+`">= 1.2"`, or whose only bound is the next major version after `0`, like
+`"0"`
+
+Requirements of `*` are covered by `wildcard_dependency` instead.
+
+### Why it is bad
+Nearly every version of the dependency is accepted, including ones with
+breaking changes. Between `0.x` versions, every minor release may be breaking.
+
+### Example
+```toml
+[dependencies]
+bar = ">= 1.2"
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = "1.2"
+```
+"#,
+    ),
+};
+
+pub fn check_overly_broad_dependency(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = OVERLY_BROAD_DEPENDENCY.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let document = manifest.document();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (table_path, deps) in dependency_tables(document) {
+        for (name, dep) in deps.iter() {
+            let mut toml_path = table_path.clone();
+            toml_path.push(name);
+            let version = match dep.as_table_like() {
+                Some(dep) => {
+                    toml_path.push("version");
+                    dep.get("version").and_then(|v| v.as_str())
+                }
+                None => dep.as_str(),
+            };
+            let Some(req) = version.and_then(|v| semver::VersionReq::parse(v).ok()) else {
+                continue;
+            };
+            if !is_overly_broad(&req) {
+                continue;
+            }
+            let Some(span) = get_span(document, &toml_path, true) else {
+                continue;
+            };
+            if is_allowed_by_comment(manifest.contents(), &span, &OVERLY_BROAD_DEPENDENCY) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let title = format!("dependency `{name}` accepts nearly every version");
+            let mut message = level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    OVERLY_BROAD_DEPENDENCY.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            let help = match tighter_requirement(&req) {
+                Some(tighter) => format!("use a caret requirement, like `\"{tighter}\"`"),
+                None => "specify the versions that are supported".to_owned(),
+            };
+            message = message.footer(Level::Help.title(&help));
+
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+/// Whether `req` has no upper bound, or only the bound of `0`
+///
+/// `*` is not considered, as `wildcard_dependency` reports it.
+fn is_overly_broad(req: &semver::VersionReq) -> bool {
+    use semver::Op;
+    !req.comparators.is_empty()
+        && req.comparators.iter().all(|c| match c.op {
+            Op::Greater | Op::GreaterEq => true,
+            Op::Exact | Op::Caret | Op::Tilde | Op::Wildcard => c.major == 0 && c.minor.is_none(),
+            _ => false,
+        })
+}
+
+/// A caret requirement starting at the lower bound of `req`, if it has a
+/// meaningful one
+fn tighter_requirement(req: &semver::VersionReq) -> Option<String> {
+    use semver::Op;
+    let lower = req
+        .comparators
+        .iter()
+        .find(|c| matches!(c.op, Op::Greater | Op::GreaterEq))?;
+    match lower.minor {
+        Some(minor) => Some(format!("{}.{minor}", lower.major)),
+        None if lower.major > 0 => Some(lower.major.to_string()),
+        None => None,
+    }
+}
+
+const PACKAGE_FIELD_ORDER: Lint = Lint {
+    name: "package_field_order",
+    desc: "`[package]` field is out of order",
+    groups: &[STYLE],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks that `name`, `version`, and `edition` are the first fields of
+`[package]`, in that order, reporting the first field that is out of order
+
+The fields can be changed with `order`, like
+`package_field_order = { level = "warn", order = ["name", "version"] }`.
+Fields that are not listed may follow them in any order.
+
+### Why it is bad
+Putting the fields that identify a package in the same place in every
+manifest makes them easier to find.
+
+### Example
+```toml
+[package]
+edition = "2021"
+name = "foo"
+version = "0.1.0"
+```
+
+Should be written as:
+```toml
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+```
+"#,
+    ),
+};
+
+/// The fields `package_field_order` expects first in `[package]` by default
+const DEFAULT_PACKAGE_FIELD_ORDER: &[&str] = &["name", "version", "edition"];
+
+pub fn check_package_field_order(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = PACKAGE_FIELD_ORDER.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    let order =
+        PACKAGE_FIELD_ORDER.string_list_option(pkg_lints, "order", DEFAULT_PACKAGE_FIELD_ORDER)?;
+
+    let document = manifest.document();
+    let Some(package) = document
+        .as_table()
+        .get("package")
+        .and_then(|p| p.as_table_like())
+    else {
+        return Ok(());
+    };
+    let mut keys = package
+        .iter()
+        .filter_map(|(key, _)| Some((key, get_span(document, &["package", key], false)?)))
+        .collect::<Vec<_>>();
+    keys.sort_by_key(|(_, span)| span.start);
+
+    // Fields that are not listed rank after every listed one
+    let rank = |key: &str| order.iter().position(|k| k == key).unwrap_or(order.len());
+    let mut expected_after: Option<&str> = None;
+    for (key, span) in keys {
+        if let Some(after) = expected_after.filter(|&after| rank(key) < rank(after)) {
+            if is_allowed_by_comment(manifest.contents(), &span, &PACKAGE_FIELD_ORDER) {
+                return Ok(());
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let manifest_path = rel_cwd_manifest_path(path, gctx);
+            let title = format!("`package.{key}` is out of order");
+            let emitted_reason = format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                PACKAGE_FIELD_ORDER.name
+            );
+            let note = format!("`{key}` is expected before `{after}`");
+            let message = level
+                .title(&title)
+                .snippet(lint_snippet(
+                    level,
+                    manifest.contents(),
+                    &manifest_path,
+                    span,
+                    [],
+                ))
+                .footer(Level::Note.title(&emitted_reason))
+                .footers(trace.iter().map(|note| Level::Note.title(note)))
+                .footer(Level::Note.title(&note));
+
+            gctx.shell().print_message(message)?;
+            return Ok(());
+        }
+        match expected_after {
+            Some(after) if rank(key) <= rank(after) => {}
+            _ => expected_after = Some(key),
+        }
+    }
+    Ok(())
+}
+
+const PANIC_SETTING_IN_LIB_PROFILE: Lint = Lint {
+    name: "panic_setting_in_lib_profile",
+    desc: "`panic` is set in a profile of a package with only a library",
+    groups: &[NURSERY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for `panic` in the `[profile]` tables of a package that only has a
+library target
+
+### Why it is bad
+The panic strategy is chosen by whatever links the final binary. A library
+used as a dependency is built with the profiles of the workspace that depends
+on it, so the setting is ignored there, and tests and benchmarks always
+unwind.
+
+### Example
+```toml
+[package]
+name = "foo"
+version = "0.1.0"
+
+[profile.release]
+panic = "abort"
+```
+where `foo` only has a `src/lib.rs`.
+"#,
+    ),
+};
+
+pub fn check_panic_setting_in_lib_profile(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = PANIC_SETTING_IN_LIB_PROFILE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let targets = manifest.targets();
+    let lib_only =
+        targets.iter().any(|t| t.is_lib()) && !targets.iter().any(|t| t.is_bin() || t.is_example());
+    if !lib_only {
+        return Ok(());
+    }
+    let document = manifest.document();
+    let Some(profiles) = document
+        .as_item()
+        .get("profile")
+        .and_then(|p| p.as_table_like())
+    else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (name, profile) in profiles.iter() {
+        if profile.get("panic").is_none() {
+            continue;
+        }
+        let Some(span) = get_span(document, &["profile", name, "panic"], false) else {
+            continue;
+        };
+        if is_allowed_by_comment(manifest.contents(), &span, &PANIC_SETTING_IN_LIB_PROFILE) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!(
+            "`profile.{name}.panic` is set, but `{}` only has a library",
+            pkg.name()
+        );
+        let mut message = level.title(&title).snippet(lint_snippet(
+            level,
+            manifest.contents(),
+            &manifest_path,
+            span,
+            [],
+        ));
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                PANIC_SETTING_IN_LIB_PROFILE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(Level::Note.title(
+            "the panic strategy is chosen when linking a binary, so it is ignored for the library",
+        ));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+const PRIVATE_DEPENDENCY_FEATURE: Lint = Lint {
+    name: "private_dependency_feature",
+    desc: "feature activates a private feature of a dependency",
+    groups: &[NURSERY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: true,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for features that activate a feature of a registry dependency whose name
+starts with `_`
+
+### Why it is bad
+By convention, features starting with `_` are internal to the crate that
+defines them and are not meant to be enabled by its dependents. They can change
+or be removed in any release.
+
+### Drawbacks
+This is only a convention, and some crates use a leading `_` for features that
+are meant to be public. The lint does not run when `cargo` is offline.
+
+### Example
+```toml
+[dependencies]
+bar = "0.1.0"
+
+[features]
+foo = ["bar/__internal"]
+```
+"#,
+    ),
+};
+
+pub fn check_private_dependency_feature(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = PRIVATE_DEPENDENCY_FEATURE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    if PRIVATE_DEPENDENCY_FEATURE.needs_registry && gctx.offline() {
+        return Ok(());
+    }
+
+    let document = manifest.document();
+    let Some(features) = document
+        .as_table()
+        .get("features")
+        .and_then(|f| f.as_table_like())
+    else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (feature, values) in features.iter() {
+        let Some(values) = values.as_array() else {
+            continue;
+        };
+        for value in values.iter().filter_map(|v| v.as_str()) {
+            let DepFeature {
+                dep_name,
+                dep_feature,
+                ..
+            } = FeatureValue::new(InternedString::new(value))
+            else {
+                continue;
+            };
+            if !dep_feature.starts_with('_') {
+                continue;
+            }
+            // Features of path and git dependencies are usually under the
+            // same author's control, so only registry dependencies matter
+            if !manifest
+                .dependencies()
+                .iter()
+                .any(|d| d.name_in_toml() == dep_name && d.source_id().is_registry())
+            {
+                continue;
+            }
+            let Some(span) = get_span(document, &["features", feature, value], true) else {
+                continue;
+            };
+            if is_allowed_by_comment(manifest.contents(), &span, &PRIVATE_DEPENDENCY_FEATURE) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let title = format!("feature `{feature}` activates private feature `{value}`");
+            let mut message = level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    PRIVATE_DEPENDENCY_FEATURE.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            let help = format!(
+                "features starting with `_` are internal to `{dep_name}` and not meant to be \
+                 enabled by dependents"
+            );
+            message = message.footer(Level::Help.title(&help));
+
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+const REDUNDANT_DEFAULT_FEATURES_TRUE: Lint = Lint {
+    name: "redundant_default_features_true",
+    desc: "`default-features = true` is redundant",
+    groups: &[STYLE],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for dependencies that set `default-features = true`
+
+Dependencies inherited with `workspace = true` are not checked, as there the
+key overrides `default-features = false` in `[workspace.dependencies]`.
+
+### Why it is bad
+Default features are enabled unless `default-features = false` is set, so the
+key has no effect.
+
+### Example
+```toml
+[dependencies]
+bar = { version = "0.1.0", default-features = true }
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0" }
+```
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+pub fn check_redundant_default_features_true(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = REDUNDANT_DEFAULT_FEATURES_TRUE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (toml_path, span) in redundant_default_features(manifest.contents(), manifest.document()) {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &REDUNDANT_DEFAULT_FEATURES_TRUE,
+                lint_level,
+                path,
+                span,
+                REDUNDANT_DEFAULT_FEATURES_TRUE.desc,
+            );
+            continue;
+        }
+        let mut message = level.title(REDUNDANT_DEFAULT_FEATURES_TRUE.desc).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                REDUNDANT_DEFAULT_FEATURES_TRUE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("remove `{}`", toml_path.last().unwrap());
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Removes the keys reported by `redundant_default_features_true` from
+/// `contents`, if the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of keys that were removed.
+pub fn fix_redundant_default_features_true(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) =
+        REDUNDANT_DEFAULT_FEATURES_TRUE.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+
+    let document: ImDocument<String> = ImDocument::parse(contents.to_owned())?;
+    let redundant = redundant_default_features(contents, &document);
+    if redundant.is_empty() {
+        return Ok(None);
+    }
+    let mut document = document.into_mut();
+    let fixes = remove_keys(&mut document, redundant);
+    Ok(Some((document.to_string(), fixes)))
+}
+
+/// Removes the key at each of `toml_paths` from `document`, returning how
+/// many were removed
+fn remove_keys(
+    document: &mut toml_edit::DocumentMut,
+    toml_paths: Vec<(Vec<String>, Range<usize>)>,
+) -> usize {
+    let mut removed = 0;
+    'keys: for (toml_path, _) in toml_paths {
+        let Some((key, parents)) = toml_path.split_last() else {
+            continue;
+        };
+        let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+        let mut is_inline = false;
+        for parent in parents {
+            let Some(next) = table.get_mut(parent) else {
+                continue 'keys;
+            };
+            is_inline = next.is_inline_table();
+            let Some(next) = next.as_table_like_mut() else {
+                continue 'keys;
+            };
+            table = next;
+        }
+        let is_last = table
+            .iter()
+            .last()
+            .is_some_and(|(last, _)| last == key.as_str());
+        let Some(item) = table.remove(key) else {
+            continue;
+        };
+        // In an inline table, the last value holds the whitespace before the
+        // closing brace, so hand it to the value that is now last
+        if is_inline && is_last {
+            let suffix = item.as_value().and_then(|v| v.decor().suffix()).cloned();
+            if let (Some(suffix), Some((_, last))) = (suffix, table.iter_mut().last()) {
+                if let Some(last) = last.as_value_mut() {
+                    last.decor_mut().set_suffix(suffix);
+                }
+            }
+        }
+        removed += 1;
+    }
+    removed
+}
+
+/// Returns every dependency table in `document`, along with its path
+fn dependency_tables(document: &ImDocument<String>) -> Vec<(Vec<&str>, &dyn toml_edit::TableLike)> {
+    let Some(root) = document.as_item().as_table_like() else {
+        return Vec::new();
+    };
+    let mut dep_tables = Vec::new();
+    for (key, item) in root.iter() {
+        if DEPENDENCY_TABLES.contains(&key) {
+            dep_tables.push((vec![key], item));
+        }
+    }
+    if let Some(targets) = root.get("target").and_then(|t| t.as_table_like()) {
+        for (platform, item) in targets.iter() {
+            let Some(platform_table) = item.as_table_like() else {
+                continue;
+            };
+            for (kind, deps) in platform_table.iter() {
+                if DEPENDENCY_TABLES.contains(&kind) {
+                    dep_tables.push((vec!["target", platform, kind], deps));
+                }
+            }
+        }
+    }
+    dep_tables
+        .into_iter()
+        .filter_map(|(path, deps)| Some((path, deps.as_table_like()?)))
+        .collect()
+}
+
+/// The kind of dependencies declared in the dependency table at `table_path`
+fn dependency_table_kind(table_path: &[&str]) -> DepKind {
+    match table_path.last() {
+        Some(&"dev-dependencies" | &"dev_dependencies") => DepKind::Development,
+        Some(&"build-dependencies" | &"build_dependencies") => DepKind::Build,
+        _ => DepKind::Normal,
+    }
+}
+
+/// Finds every dependency that sets `default-features = true`, as the path to
+/// and span of that key, skipping any that are allowed by a `# cargo::allow`
+/// comment
+fn redundant_default_features(
+    contents: &str,
+    document: &ImDocument<String>,
+) -> Vec<(Vec<String>, Range<usize>)> {
+    let mut redundant = Vec::new();
+    for (table_path, deps) in dependency_tables(document) {
+        for (name, dep) in deps.iter() {
+            let Some(dep) = dep.as_table_like() else {
+                continue;
+            };
+            // Overrides `default-features = false` from `[workspace.dependencies]`
+            if dep.get("workspace").and_then(|v| v.as_bool()) == Some(true) {
+                continue;
+            }
+            for key in ["default-features", "default_features"] {
+                if dep.get(key).and_then(|v| v.as_bool()) != Some(true) {
+                    continue;
+                }
+                let mut toml_path = table_path.clone();
+                toml_path.extend([name, key]);
+                let Some(span) = get_span(document, &toml_path, false) else {
+                    continue;
+                };
+                if is_allowed_by_comment(contents, &span, &REDUNDANT_DEFAULT_FEATURES_TRUE) {
+                    continue;
+                }
+                redundant.push((toml_path.into_iter().map(String::from).collect(), span));
+            }
+        }
+    }
+    redundant
+}
+
+const REDUNDANT_OPTIONAL_FALSE: Lint = Lint {
+    name: "redundant_optional_false",
+    desc: "`optional = false` is redundant",
+    groups: &[STYLE],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[(Edition::Edition2024, LintLevel::Warn)],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for dependencies that set `optional = false`
+
+### Why it is bad
+Dependencies are required unless `optional = true` is set, so the key has no
+effect.
+
+### Example
+```toml
+[dependencies]
+bar = { version = "0.1.0", optional = false }
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0" }
+```
+
+This lint is set to `warn` as of the 2024 edition, and `cargo fix --edition`
+removes the key when migrating to it with `-Zcargo-lints`.
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+pub fn check_redundant_optional_false(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = REDUNDANT_OPTIONAL_FALSE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (_, span) in redundant_optional(manifest.contents(), manifest.document()) {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &REDUNDANT_OPTIONAL_FALSE,
+                lint_level,
+                path,
+                span,
+                REDUNDANT_OPTIONAL_FALSE.desc,
+            );
+            continue;
+        }
+        let mut message = level
+            .title(REDUNDANT_OPTIONAL_FALSE.desc)
+            .snippet(lint_snippet(
+                level,
+                manifest.contents(),
+                &manifest_path,
+                span,
+                [],
+            ));
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                REDUNDANT_OPTIONAL_FALSE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(Level::Help.title("remove `optional`"));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Removes the keys reported by `redundant_optional_false` from `contents`,
+/// if the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of keys that were removed.
+pub fn fix_redundant_optional_false(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) =
+        REDUNDANT_OPTIONAL_FALSE.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+
+    let document: ImDocument<String> = ImDocument::parse(contents.to_owned())?;
+    let redundant = redundant_optional(contents, &document);
+    if redundant.is_empty() {
+        return Ok(None);
+    }
+    let mut document = document.into_mut();
+    let fixes = remove_keys(&mut document, redundant);
+    Ok(Some((document.to_string(), fixes)))
+}
+
+/// Finds every dependency that sets `optional = false`, as the path to and
+/// span of that key, skipping any that are allowed by a `# cargo::allow`
+/// comment
+fn redundant_optional(
+    contents: &str,
+    document: &ImDocument<String>,
+) -> Vec<(Vec<String>, Range<usize>)> {
+    let mut redundant = Vec::new();
+    for (table_path, deps) in dependency_tables(document) {
+        for (name, dep) in deps.iter() {
+            let Some(dep) = dep.as_table_like() else {
+                continue;
+            };
+            if dep.get("optional").and_then(|v| v.as_bool()) != Some(false) {
+                continue;
+            }
+            let mut toml_path = table_path.clone();
+            toml_path.extend([name, "optional"]);
+            let Some(span) = get_span(document, &toml_path, false) else {
+                continue;
+            };
+            if is_allowed_by_comment(contents, &span, &REDUNDANT_OPTIONAL_FALSE) {
+                continue;
+            }
+            redundant.push((toml_path.into_iter().map(String::from).collect(), span));
+        }
+    }
+    redundant
+}
+
+const REDUNDANT_TARGET_DEPENDENCY: Lint = Lint {
+    name: "redundant_target_dependency",
+    desc: "target table whose `cfg` is always or never true",
+    groups: &[COMPLEXITY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MaybeIncorrect,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for `[target]` tables keyed by a `cfg(...)` expression that holds for
+every platform, like `cfg(all())`, or for no platform, like `cfg(any())`
+
+### Why it is bad
+A table that always applies is a roundabout way of writing `[dependencies]`,
+and the dependencies in a table that never applies are never used.
+
+### Example
+```toml
+[target.'cfg(all())'.dependencies]
+bar = "0.1.0"
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = "0.1.0"
+```
+"#,
+    ),
+};
+
+pub fn check_redundant_target_dependency(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = REDUNDANT_TARGET_DEPENDENCY.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let document = manifest.document();
+    let Some(targets) = document
+        .as_item()
+        .as_table_like()
+        .and_then(|root| root.get("target"))
+        .and_then(|t| t.as_table_like())
+    else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (key, _) in targets.iter() {
+        let Ok(Platform::Cfg(expr)) = key.parse::<Platform>() else {
+            continue;
+        };
+        let (title, help) = if cfg_is_tautology(&expr) {
+            (
+                format!("`{key}` is always true"),
+                "move the dependencies to `[dependencies]`",
+            )
+        } else if cfg_is_contradiction(&expr) {
+            (
+                format!("`{key}` is never true"),
+                "remove the table, its dependencies are never used",
+            )
+        } else {
+            continue;
+        };
+        let Some(span) = get_span(document, &["target", key], false) else {
+            continue;
+        };
+        if is_allowed_by_comment(manifest.contents(), &span, &REDUNDANT_TARGET_DEPENDENCY) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let mut message = level.title(&title).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                REDUNDANT_TARGET_DEPENDENCY.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(Level::Help.title(help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Whether `expr` holds regardless of the platform, e.g. `all()`
+fn cfg_is_tautology(expr: &CfgExpr) -> bool {
+    match expr {
+        CfgExpr::Not(e) => cfg_is_contradiction(e),
+        CfgExpr::All(es) => es.iter().all(cfg_is_tautology),
+        CfgExpr::Any(es) => es.iter().any(cfg_is_tautology),
+        CfgExpr::Value(_) => false,
+    }
+}
+
+/// Whether `expr` does not hold for any platform, e.g. `any()`
+fn cfg_is_contradiction(expr: &CfgExpr) -> bool {
+    match expr {
+        CfgExpr::Not(e) => cfg_is_tautology(e),
+        CfgExpr::All(es) => es.iter().any(cfg_is_contradiction),
+        CfgExpr::Any(es) => es.iter().all(cfg_is_contradiction),
+        CfgExpr::Value(_) => false,
+    }
+}
+
+const SCRIPT_INCOMPATIBLE_KEY: Lint = Lint {
+    name: "script_incompatible_key",
+    desc: "manifest key is not supported in single-file packages",
+    groups: &[NURSERY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for manifest keys that are not allowed in the embedded manifest of a
+single-file package
+
+### Why it is bad
+A package using these keys cannot be turned into a single-file package
+without first removing them, as the embedded manifest will fail to parse.
+
+### Example
+```toml
+[package]
+name = "foo"
+version = "0.1.0"
+links = "foo"
+```
+"#,
+    ),
+};
+
+pub fn check_script_incompatible_key(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    // Embedded manifests already reject these keys
+    if manifest.is_embedded() {
+        return Ok(());
+    }
+    let (lint_level, reason, trace) = SCRIPT_INCOMPATIBLE_KEY.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let toml_paths = DISALLOWED_KEYS
+        .iter()
+        .map(|key| vec![*key])
+        .chain(disallowed_package_keys().map(|key| vec!["package", key]));
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for toml_path in toml_paths {
+        let Some(span) = get_span(manifest.document(), &toml_path, false) else {
+            continue;
+        };
+        if is_allowed_by_comment(manifest.contents(), &span, &SCRIPT_INCOMPATIBLE_KEY) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!(
+            "`{}` is not supported in single-file packages",
+            toml_path.join(".")
+        );
+        let mut message = level.title(&title).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                SCRIPT_INCOMPATIBLE_KEY.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+const SELF_REFERENTIAL_FEATURE: Lint = Lint {
+    name: "self_referential_feature",
+    desc: "feature activates itself",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for features that list themselves
+
+### Why it is bad
+A feature is always activated along with itself, so listing it has no effect.
+This is likely a typo for another feature or dependency.
+
+### Example
+```toml
+[features]
+foo = ["foo", "bar"]
+bar = []
+```
+
+Should be written as:
+```toml
+[features]
+foo = ["bar"]
+bar = []
+```
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+pub fn check_self_referential_feature(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = SELF_REFERENTIAL_FEATURE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (feature, span) in self_referential_features(manifest.contents(), manifest.document()) {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("feature `{feature}` activates itself");
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &SELF_REFERENTIAL_FEATURE,
+                lint_level,
+                path,
+                span,
+                &title,
+            );
+            continue;
+        }
+        let mut message = level.title(&title).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                SELF_REFERENTIAL_FEATURE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("remove `\"{feature}\"`");
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Removes the entries reported by `self_referential_feature` from
+/// `contents`, if the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of entries that were removed.
+pub fn fix_self_referential_feature(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) =
+        SELF_REFERENTIAL_FEATURE.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+
+    let document: ImDocument<String> = ImDocument::parse(contents.to_owned())?;
+    let self_referential = self_referential_features(contents, &document);
+    if self_referential.is_empty() {
+        return Ok(None);
+    }
+    let mut document = document.into_mut();
+    let Some(features) = document
+        .as_table_mut()
+        .get_mut("features")
+        .and_then(|item| item.as_table_like_mut())
+    else {
+        return Ok(None);
+    };
+    let mut fixes = 0;
+    for (feature, _) in self_referential {
+        let Some(values) = features
+            .get_mut(&feature)
+            .and_then(|item| item.as_array_mut())
+        else {
+            continue;
+        };
+        // Removing an entry can leave its successor with the separating
+        // whitespace, so take over the removed entry's instead
+        let mut prefix = None;
+        let mut i = 0;
+        while i < values.len() {
+            if values.get(i).and_then(|v| v.as_str()) != Some(feature.as_str()) {
+                if let Some(prefix) = prefix.take() {
+                    values.get_mut(i).unwrap().decor_mut().set_prefix(prefix);
+                }
+                i += 1;
+                continue;
+            }
+            let removed = values.remove(i);
+            if i == 0 && prefix.is_none() {
+                prefix = removed.decor().prefix().cloned();
+            }
+            fixes += 1;
+        }
+    }
+    Ok(Some((document.to_string(), fixes)))
+}
+
+/// Finds every feature that lists itself, as the name of the feature and the
+/// span of the entry, skipping any that are allowed by a `# cargo::allow`
+/// comment
+fn self_referential_features(
+    contents: &str,
+    document: &ImDocument<String>,
+) -> Vec<(String, Range<usize>)> {
+    let mut self_referential = Vec::new();
+    let Some(features) = document
+        .as_table()
+        .get("features")
+        .and_then(|f| f.as_table_like())
+    else {
+        return self_referential;
+    };
+    for (feature, values) in features.iter() {
+        let Some(values) = values.as_array() else {
+            continue;
+        };
+        if !values.iter().any(|v| v.as_str() == Some(feature)) {
+            continue;
+        }
+        let Some(span) = get_span(document, &["features", feature, feature], true) else {
+            continue;
+        };
+        if is_allowed_by_comment(contents, &span, &SELF_REFERENTIAL_FEATURE) {
+            continue;
+        }
+        self_referential.push((feature.to_owned(), span));
+    }
+    self_referential
+}
+
+const SUSPICIOUS_KEY_CASING: Lint = Lint {
+    name: "suspicious_key_casing",
+    desc: "suspicious casing of manifest key",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for camelCase spellings of known manifest keys, at the top-level of
+the manifest and within dependencies
+
+### Why it is bad
+Manifest keys are kebab-case, so a key like `defaultFeatures` is not
+recognized. It is ignored, with only an "unused manifest key" warning, and the
+setting it was meant to apply never takes effect.
+
+### Example
+```toml
+[dependencies]
+bar = { version = "0.1.0", defaultFeatures = false }
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0", default-features = false }
+```
+"#,
+    ),
+};
+
+const TOP_LEVEL_KEYS: &[&str] = &[
+    "badges",
+    "bench",
+    "bin",
+    "build-dependencies",
+    "cargo-features",
+    "dependencies",
+    "dev-dependencies",
+    "example",
+    "features",
+    "lib",
+    "lints",
+    "package",
+    "patch",
+    "profile",
+    "replace",
+    "target",
+    "test",
+    "workspace",
+];
+
+const DEPENDENCY_KEYS: &[&str] = &[
+    "artifact",
+    "branch",
+    "default-features",
+    "features",
+    "git",
+    "lib",
+    "optional",
+    "package",
+    "path",
+    "public",
+    "registry",
+    "registry-index",
+    "rev",
+    "tag",
+    "target",
+    "version",
+    "workspace",
+];
+
+const DEPENDENCY_TABLES: &[&str] = &[
+    "dependencies",
+    "dev-dependencies",
+    "dev_dependencies",
+    "build-dependencies",
+    "build_dependencies",
+];
+
+/// Returns the known key that `key` is a camelCase spelling of, if any
+fn kebab_case_key(key: &str, known: &[&'static str]) -> Option<&'static str> {
+    if !key.chars().any(|c| c.is_ascii_uppercase()) {
+        return None;
+    }
+    let mut kebab = String::new();
+    for (i, c) in key.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i != 0 {
+                kebab.push('-');
+            }
+            kebab.push(c.to_ascii_lowercase());
+        } else {
+            kebab.push(c);
+        }
+    }
+    known.iter().find(|k| **k == kebab).copied()
+}
+
+pub fn check_suspicious_key_casing(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = SUSPICIOUS_KEY_CASING.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    let Some(root) = manifest.document().as_item().as_table_like() else {
+        return Ok(());
+    };
+
+    // Each suspicious key as `(toml_path, expected_key)`
+    let mut suspicious = Vec::new();
+    for (key, _) in root.iter() {
+        if let Some(expected) = kebab_case_key(key, TOP_LEVEL_KEYS) {
+            suspicious.push((vec![key], expected));
+        }
+    }
+    for (table_path, deps) in dependency_tables(manifest.document()) {
+        for (name, dep) in deps.iter() {
+            let Some(dep) = dep.as_table_like() else {
+                continue;
+            };
+            for (key, _) in dep.iter() {
+                if let Some(expected) = kebab_case_key(key, DEPENDENCY_KEYS) {
+                    let mut toml_path = table_path.clone();
+                    toml_path.extend([name, key]);
+                    suspicious.push((toml_path, expected));
+                }
+            }
+        }
+    }
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (toml_path, expected) in suspicious {
+        let Some(span) = get_span(manifest.document(), &toml_path, false) else {
+            continue;
+        };
+        if is_allowed_by_comment(manifest.contents(), &span, &SUSPICIOUS_KEY_CASING) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let mut message = level.title(SUSPICIOUS_KEY_CASING.desc).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                SUSPICIOUS_KEY_CASING.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("did you mean `{expected}`?");
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+const TAB_INDENTATION: Lint = Lint {
+    name: "tab_indentation",
+    desc: "manifest line is indented with tabs",
+    groups: &[STYLE],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MachineApplicable,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for lines in the manifest that are indented with tabs
+
+`cargo fix` replaces each tab with 4 spaces, which can be changed with
+`tab-width`, like `tab_indentation = { level = "warn", tab-width = 2 }`.
+
+### Why it is bad
+Tabs are displayed with different widths by different editors, so mixing them
+with spaces makes the indentation inconsistent. Some projects require spaces.
+
+### Example
+```toml
+[dependencies]
+bar = { version = "0.1.0", features = [
+→   "std",
+] }
+```
+where `→   ` is a tab.
+
+Should be written as:
+```toml
+[dependencies]
+bar = { version = "0.1.0", features = [
+    "std",
+] }
+```
+
+This lint can be fixed automatically with `cargo fix`.
+"#,
+    ),
+};
+
+/// The number of spaces `tab_indentation` replaces a tab with by default
+const DEFAULT_TAB_WIDTH: u64 = 4;
+
+pub fn check_tab_indentation(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    message_format: MessageFormat,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = TAB_INDENTATION.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for span in tab_indentation_spans(manifest.contents(), manifest.document()) {
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        if let MessageFormat::Json { .. } = message_format {
+            print_json(
+                gctx,
+                &TAB_INDENTATION,
+                lint_level,
+                path,
+                span,
+                TAB_INDENTATION.desc,
+            );
+            continue;
+        }
+        let mut message = level.title(TAB_INDENTATION.desc).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                TAB_INDENTATION.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(Level::Help.title("indent with spaces instead"));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Replaces the tabs reported by `tab_indentation` in `contents` with spaces,
+/// if the lint is enabled for `pkg`
+///
+/// Returns the fixed contents and the number of lines that were changed.
+pub fn fix_tab_indentation(
+    pkg: &Package,
+    contents: &str,
+    pkg_lints: &TomlToolLints,
+    edition: Edition,
+) -> CargoResult<Option<(String, usize)>> {
+    let manifest = pkg.manifest();
+    let (lint_level, _) = TAB_INDENTATION.level(pkg_lints, edition, manifest.unstable_features());
+    if lint_level == LintLevel::Allow {
+        return Ok(None);
+    }
+    let tab_width = TAB_INDENTATION.integer_option(pkg_lints, "tab-width", DEFAULT_TAB_WIDTH)?;
+
+    let document: ImDocument<String> = ImDocument::parse(contents.to_owned())?;
+    let spans = tab_indentation_spans(contents, &document);
+    if spans.is_empty() {
+        return Ok(None);
+    }
+    let spaces = " ".repeat(tab_width as usize);
+    let mut fixed = String::with_capacity(contents.len());
+    let mut last = 0;
+    for span in &spans {
+        fixed.push_str(&contents[last..span.start]);
+        fixed.push_str(&contents[span.clone()].replace('\t', &spaces));
+        last = span.end;
+    }
+    fixed.push_str(&contents[last..]);
+    Ok(Some((fixed, spans.len())))
+}
+
+/// Gets the spans of the indentation of each line of `contents` that contains a
+/// tab, skipping lines that are part of a multi-line string value or allowed
+/// by a `# cargo::allow` comment
+fn tab_indentation_spans(contents: &str, document: &ImDocument<String>) -> Vec<Range<usize>> {
+    let strings = multiline_string_spans(document);
+
+    let mut spans = Vec::new();
+    let mut line_start = 0;
+    for line in contents.split_inclusive('\n') {
+        let indent = line.len() - line.trim_start_matches([' ', '\t']).len();
+        let span = line_start..line_start + indent;
+        line_start += line.len();
+        if !contents[span.clone()].contains('\t')
+            || strings
+                .iter()
+                .any(|s| s.start < span.start && span.end <= s.end)
+            || is_allowed_by_comment(contents, &span, &TAB_INDENTATION)
+        {
+            continue;
+        }
+        spans.push(span);
+    }
+    spans
+}
+
+const TARGET_PLATFORM_TRIPLE_TYPO: Lint = Lint {
+    name: "target_platform_triple_typo",
+    desc: "target table for a misspelled platform triple",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MaybeIncorrect,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for `[target]` tables keyed by a platform triple that `rustc` does not
+know, but that is close to one it does
+
+Tables keyed by a `cfg(...)` expression are not checked. Keys that are not
+close to any known triple are assumed to be custom targets.
+
+### Why it is bad
+A misspelled triple never matches the platform being built for, so the
+dependencies in the table are silently never used.
+
+### Example
+```toml
+[target.x86_64-unknow-linux-gnu.dependencies]
+libc = "0.2"
+```
+
+Should be written as:
+```toml
+[target.x86_64-unknown-linux-gnu.dependencies]
+libc = "0.2"
+```
+"#,
+    ),
+};
+
+pub fn check_target_platform_triple_typo(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = TARGET_PLATFORM_TRIPLE_TYPO.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let document = manifest.document();
+    let Some(targets) = document
+        .as_item()
+        .as_table_like()
+        .and_then(|root| root.get("target"))
+        .and_then(|t| t.as_table_like())
+    else {
+        return Ok(());
+    };
+    let triples = targets
+        .iter()
+        .map(|(key, _)| key)
+        .filter(|key| !key.trim_start().starts_with("cfg("))
+        .collect::<Vec<_>>();
+    if triples.is_empty() {
+        return Ok(());
+    }
+    // Without a target list from `rustc` there is nothing to compare against
+    let Some(known) = known_target_triples(gctx) else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for triple in triples {
+        if known.iter().any(|known| known == triple) {
+            continue;
+        }
+        let Some(suggestion) = closest(triple, known.iter(), |known| known.as_str()) else {
+            continue;
+        };
+        let Some(span) = get_span(document, &["target", triple], false) else {
+            continue;
+        };
+        if is_allowed_by_comment(manifest.contents(), &span, &TARGET_PLATFORM_TRIPLE_TYPO) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("`{triple}` is not a known target triple");
+        let mut message = level.title(&title).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                TARGET_PLATFORM_TRIPLE_TYPO.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("there is a target with a similar name: `{suggestion}`");
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// The target triples built into `rustc`, or `None` if they could not be listed
+fn known_target_triples(gctx: &GlobalContext) -> Option<Vec<String>> {
+    let rustc = gctx.load_global_rustc(None).ok()?;
+    let mut cmd = rustc.process();
+    cmd.arg("--print").arg("target-list");
+    let (stdout, _) = rustc.cached_output(&cmd, 0).ok()?;
+    Some(stdout.lines().map(str::to_owned).collect())
+}
+
+const UNKNOWN_DEPENDENCY_FEATURE: Lint = Lint {
+    name: "unknown_dependency_feature",
+    desc: "dependency feature that the dependency does not have",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MaybeIncorrect,
+    needs_registry: true,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks that the `features` requested for a registry dependency are features the
+dependency has, according to the registry index
+
+### Why it is bad
+A misspelled feature enables nothing, and is only reported once dependencies
+are resolved, if at all.
+
+### Drawbacks
+This needs to query the registry index, so it does not run when `cargo` is
+offline.
+
+### Example
+```toml
+[dependencies]
+serde = { version = "1.0", features = ["derve"] }
+```
+
+Should be written as:
+```toml
+[dependencies]
+serde = { version = "1.0", features = ["derive"] }
+```
+"#,
+    ),
+};
+
+pub fn check_unknown_dependency_feature(
+    ws: &Workspace<'_>,
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = UNKNOWN_DEPENDENCY_FEATURE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    if UNKNOWN_DEPENDENCY_FEATURE.needs_registry && gctx.offline() {
+        return Ok(());
+    }
+
+    let document = manifest.document();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    // Only lock the package cache and load the registry once a dependency
+    // needs it
+    let mut registry = None;
+    let mut emitted_source = None;
+    for (table_path, deps) in dependency_tables(document) {
+        let kind = dependency_table_kind(&table_path);
+        for (name, dep) in deps.iter() {
+            let Some(features) = dep
+                .as_table_like()
+                .and_then(|dep| dep.get("features"))
+                .and_then(|f| f.as_array())
+            else {
+                continue;
+            };
+            let Some(dependency) = manifest.dependencies().iter().find(|d| {
+                d.name_in_toml() == name && d.kind() == kind && d.source_id().is_registry()
+            }) else {
+                continue;
+            };
+            if registry.is_none() {
+                let lock = gctx.acquire_package_cache_lock(CacheLockMode::DownloadExclusive)?;
+                let mut package_registry = ws.package_registry()?;
+                package_registry.lock_patches();
+                registry = Some((package_registry, lock));
+            }
+            let (package_registry, _) = registry.as_mut().unwrap();
+            let summaries = loop {
+                match package_registry.query_vec(dependency, QueryKind::Exact) {
+                    Poll::Ready(res) => break res,
+                    Poll::Pending => package_registry.block_until_ready()?,
+                }
+            };
+            // The index could not be reached, or has no matching version, so
+            // there is nothing to check the features against
+            let Ok(summaries) = summaries else {
+                continue;
+            };
+            if summaries.is_empty() {
+                continue;
+            }
+            let known = summaries
+                .iter()
+                .flat_map(|s| s.as_summary().features().keys().copied())
+                .collect::<HashSet<_>>();
+
+            for feature in features.iter().filter_map(|f| f.as_str()) {
+                if feature == "default"
+                    || feature.contains('/')
+                    || known.contains(&InternedString::new(feature))
+                {
+                    continue;
+                }
+                let mut toml_path = table_path.clone();
+                toml_path.extend([name, "features", feature]);
+                let Some(span) = get_span(document, &toml_path, true) else {
+                    continue;
+                };
+                if is_allowed_by_comment(manifest.contents(), &span, &UNKNOWN_DEPENDENCY_FEATURE) {
+                    continue;
+                }
+                if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                    *error_count += 1;
+                }
+                let title = format!(
+                    "feature `{feature}` is not a feature of `{}`",
+                    dependency.package_name()
+                );
+                let mut message = level.title(&title).snippet(
+                    Snippet::source(manifest.contents())
+                        .origin(&manifest_path)
+                        .annotation(level.span(span))
+                        .fold(true),
+                );
+                if emitted_source.is_none() {
+                    emitted_source = Some(format!(
+                        "`cargo::{}` is set to `{lint_level}` {reason}",
+                        UNKNOWN_DEPENDENCY_FEATURE.name
+                    ));
+                    message = message
+                        .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                        .footers(trace.iter().map(|note| Level::Note.title(note)));
+                }
+                let help = closest(feature, known.iter(), |f| f.as_str())
+                    .map(|f| format!("there is a feature with a similar name: `{f}`"));
+                if let Some(help) = &help {
+                    message = message.footer(Level::Help.title(help));
+                }
+
+                gctx.shell().print_message(message)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+const UNKNOWN_LINTS: Lint = Lint {
+    name: "unknown_lints",
+    desc: "unknown lint",
+    groups: &[],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::MaybeIncorrect,
+    needs_registry: false,
+    since: "1.78",
+    docs: Some(
+        r#"
+### What it does
+Checks for unknown lints in the `[lints.cargo]` table
+
+### Why it is bad
+- The lint name could be misspelled, leading to confusion as to why it is
+  not working as expected
+- The unknown lint could end up causing an error if `cargo` decides to make
+  a lint with the same name in the future
+
+### Example
+```toml
+[lints.cargo]
+this-lint-does-not-exist = "warn"
+```
+"#,
+    ),
+};
+
+fn output_unknown_lint(
+    lint_name: &str,
+    manifest: &Manifest,
+    manifest_path: &str,
+    pkg_lints: &TomlToolLints,
+    ws_contents: &str,
+    ws_document: &ImDocument<String>,
+    ws_path: &str,
+    message_format: MessageFormat,
+    emitted_source: &mut bool,
+    report: &mut Vec<String>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) {
+    let (lint_level, reason, trace) = UNKNOWN_LINTS.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return;
+    };
+
+    if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+        *error_count += 1;
+    }
+    let title = format!("{}: `{lint_name}`", UNKNOWN_LINTS.desc);
+    let ws_title = format!("{title} (from workspace manifest)");
+    let second_title = format!("`cargo::{}` was inherited", lint_name);
+    let underscore_lint_name = lint_name.replace("-", "_");
+    let matching = if let Some(lint) = LINTS.iter().find(|l| l.name == underscore_lint_name) {
+        Some((lint.name, "lint"))
+    } else if let Some(group) = LINT_GROUPS
+        .iter()
+        .chain(DYNAMIC_LINT_GROUPS)
+        .find(|g| g.name == underscore_lint_name)
+    {
+        Some((group.name, "group"))
+    } else {
+        LINTS
+            .iter()
+            .map(|l| (l.name, "lint"))
+            .chain(
+                LINT_GROUPS
+                    .iter()
+                    .chain(DYNAMIC_LINT_GROUPS)
+                    .map(|g| (g.name, "group")),
+            )
+            .filter_map(|(name, kind)| {
+                Some((edit_distance(&underscore_lint_name, name, 2)?, name, kind))
+            })
+            .min_by_key(|(distance, _, _)| *distance)
+            .map(|(_, name, kind)| (name, kind))
+    };
+
+    // Where the unknown lint was written: `(contents, path, span, is_workspace)`
+    let location =
+        if let Some(span) = get_span(manifest.document(), &["lints", "cargo", lint_name], false) {
+            Some((manifest.contents(), manifest_path, span, false))
+        } else if let Some(span) = get_span(
+            ws_document,
+            &["workspace", "lints", "cargo", lint_name],
+            false,
+        ) {
+            Some((ws_contents, ws_path, span, true))
+        } else {
+            None
+        };
+    let title = match location {
+        Some((_, _, _, true)) => &ws_title,
+        _ => &title,
+    };
+
+    if let MessageFormat::Json { .. } = message_format {
+        let (origin, span) = location
+            .clone()
+            .map_or((manifest_path, None), |(_, path, span, _)| {
+                (path, Some(span))
+            });
+        let msg = machine_message::ManifestLint {
+            manifest_path: paths::normalize_path(&gctx.cwd().join(origin)),
+            name: UNKNOWN_LINTS.name,
+            level: lint_level.to_string(),
+            message: title,
+            span,
+            applicability: UNKNOWN_LINTS.applicability,
+            since: UNKNOWN_LINTS.since,
+            suggestion: matching.map(|(name, kind)| machine_message::LintSuggestion {
+                name,
+                kind,
+                applicability: UNKNOWN_LINTS.applicability,
+            }),
+        }
+        .to_json_string();
+        crate::drop_println!(gctx, "{}", msg);
+        return;
+    }
+
+    if let MessageFormat::Short = message_format {
+        let location = location.map(|(contents, path, span, _)| (contents, path, span));
+        report.push(render_short(
+            manifest_path,
+            location,
+            level,
+            UNKNOWN_LINTS.name,
+            title,
+        ));
+        return;
+    }
+
+    let help =
+        matching.map(|(name, kind)| format!("there is a {kind} with a similar name: `{name}`"));
+
+    let mut message = match location {
+        Some((contents, path, span, false)) => level.title(title).snippet(
+            Snippet::source(contents)
+                .origin(path)
+                .annotation(Level::Error.span(span))
+                .fold(true),
+        ),
+        Some((contents, path, span, true)) => {
+            let inherited_note = if let (Some(inherit_span_key), Some(inherit_span_value)) = (
+                get_span(manifest.document(), &["lints", "workspace"], false),
+                get_span(manifest.document(), &["lints", "workspace"], true),
+            ) {
+                Level::Note.title(&second_title).snippet(
+                    Snippet::source(manifest.contents())
+                        .origin(&manifest_path)
+                        .annotation(
+                            Level::Note.span(inherit_span_key.start..inherit_span_value.end),
+                        )
+                        .fold(true),
+                )
+            } else {
+                Level::Note.title(&second_title)
+            };
+
+            level
+                .title(title)
+                .snippet(
+                    Snippet::source(contents)
+                        .origin(path)
+                        .annotation(Level::Error.span(span))
+                        .fold(true),
+                )
+                .footer(inherited_note)
+        }
+        // The lint could not be located in either manifest, so there is
+        // nothing to point at
+        None => level.title(title),
+    };
+
+    let source = format!(
+        "`cargo::{}` is set to `{lint_level}` {reason}",
+        UNKNOWN_LINTS.name
+    );
+    if !*emitted_source {
+        *emitted_source = true;
+        message = message
+            .footer(Level::Note.title(&source))
+            .footers(trace.iter().map(|note| Level::Note.title(note)));
+    }
+
+    if let Some(help) = help.as_ref() {
+        message = message.footer(Level::Help.title(help));
+    }
+
+    report.push(gctx.shell().render_message(message));
+}
+
+const UNKNOWN_PROFILE_FIELD: Lint = Lint {
+    name: "unknown_profile_field",
+    desc: "unknown field in profile",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for fields in `[profile]` tables that `cargo` does not know about, and
+for `lto` values that are not valid
+
+### Why it is bad
+An unknown field is ignored, so a typo silently leaves the setting at its
+default. An invalid `lto` value is only rejected once `rustc` is run.
+
+### Example
+```toml
+[profile.release]
+optlevel = 2
+lto = "tru"
+```
+
+Should be written as:
+```toml
+[profile.release]
+opt-level = 2
+lto = true
+```
+"#,
+    ),
+};
+
+/// Fields of a `[profile]` table, see `TomlProfile`
+const PROFILE_FIELDS: &[&str] = &[
+    "opt-level",
+    "lto",
+    "codegen-backend",
+    "codegen-units",
+    "debug",
+    "split-debuginfo",
+    "debug-assertions",
+    "rpath",
+    "panic",
+    "overflow-checks",
+    "incremental",
+    "dir-name",
+    "inherits",
+    "strip",
+    "rustflags",
+    "package",
+    "build-override",
+    "trim-paths",
+];
+
+/// String values of `lto`, besides booleans
+///
+/// `"true"` and `"false"` are rejected when the manifest is loaded.
+const LTO_VALUES: &[&str] = &["thin", "fat", "off"];
+
+pub fn check_unknown_profile_field(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = UNKNOWN_PROFILE_FIELD.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let document = manifest.document();
+    let Some(profiles) = document
+        .as_item()
+        .get("profile")
+        .and_then(|p| p.as_table_like())
+    else {
+        return Ok(());
+    };
+    // Each profile along with its `package` and `build-override` overrides
+    let mut tables = Vec::new();
+    for (name, profile) in profiles.iter() {
+        let Some(profile) = profile.as_table_like() else {
+            continue;
+        };
+        tables.push((vec!["profile", name], profile));
+        if let Some(packages) = profile.get("package").and_then(|p| p.as_table_like()) {
+            for (spec, overrides) in packages.iter() {
+                if let Some(overrides) = overrides.as_table_like() {
+                    tables.push((vec!["profile", name, "package", spec], overrides));
+                }
+            }
+        }
+        if let Some(overrides) = profile
+            .get("build-override")
+            .and_then(|p| p.as_table_like())
+        {
+            tables.push((vec!["profile", name, "build-override"], overrides));
+        }
+    }
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (table_path, table) in tables {
+        for (key, value) in table.iter() {
+            let table_name = table_path.join(".");
+            let lto = (key == "lto").then(|| value.as_str()).flatten();
+            let (title, help, get_value) = if !PROFILE_FIELDS.contains(&key) {
+                let title = format!("unknown field `{key}` in `{table_name}`");
+                let help = closest(key, PROFILE_FIELDS.iter(), |field| **field)
+                    .map(|field| format!("a field with a similar name exists: `{field}`"));
+                (title, help, false)
+            } else if let Some(lto) = lto.filter(|lto| !LTO_VALUES.contains(lto)) {
+                let title = format!("`lto` setting of `\"{lto}\"` in `{table_name}` is not valid");
+                let help = Some("use a boolean, `\"thin\"`, `\"fat\"`, or `\"off\"`".to_owned());
+                (title, help, true)
+            } else {
+                // `panic` and other values are validated when the manifest is loaded
+                continue;
+            };
+            let mut toml_path = table_path.clone();
+            toml_path.push(key);
+            let Some(span) = get_span(document, &toml_path, get_value) else {
+                continue;
+            };
+            if is_allowed_by_comment(manifest.contents(), &span, &UNKNOWN_PROFILE_FIELD) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let mut message = level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    UNKNOWN_PROFILE_FIELD.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            if let Some(help) = &help {
+                message = message.footer(Level::Help.title(help));
+            }
+
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+const UNUSED_FEATURE: Lint = Lint {
+    name: "unused_feature",
+    desc: "unused feature",
+    groups: &[NURSERY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for features that enable nothing and are not used anywhere in the
+package
+
+A feature is considered unused when it has an empty body, no other feature
+enables it, and no source file of the package checks for it with
+`cfg(feature = "...")` (or a build script with `CARGO_FEATURE_<name>`).
+
+### Why it is bad
+A feature that has no effect is likely left over from a refactor, and
+dependents enabling it get nothing for it.
+
+### Drawbacks
+Sources are scanned textually, so a feature that is only checked in code
+generated by a macro or a build script will be reported.
+
+### Example
+```toml
+[features]
+default = ["std"]
+std = []
+unused = []
+```
+where only `std` appears in a `cfg(feature = "...")`.
+"#,
+    ),
+};
+
+pub fn check_unused_feature(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = UNUSED_FEATURE.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    // Scanning needs the sources on disk, which is only guaranteed for path
+    // packages
+    if !pkg.package_id().source_id().is_path() {
+        return Ok(());
+    }
+
+    let features = manifest.summary().features();
+    let referenced = features
+        .values()
+        .flatten()
+        .filter_map(|fv| match fv {
+            FeatureValue::Feature(f) => Some(f.as_str()),
+            _ => None,
+        })
+        // A target's `required-features` counts as a use of the feature
+        .chain(
+            manifest
+                .targets()
+                .iter()
+                .filter_map(|t| t.required_features())
+                .flatten()
+                .map(|f| f.as_str()),
+        )
+        .collect::<HashSet<_>>();
+    let candidates = features
+        .iter()
+        .filter(|(name, values)| {
+            name.as_str() != "default" && values.is_empty() && !referenced.contains(name.as_str())
+        })
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    if candidates.is_empty() {
+        return Ok(());
+    }
+    // Listing the sources failed, so we can't tell which features are used
+    let Some(used) = cfg_features_in_sources(pkg, gctx) else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for name in candidates {
+        if used.contains(name) {
+            continue;
+        }
+        let Some(span) = get_span(manifest.document(), &["features", name], false) else {
+            continue;
+        };
+        if is_allowed_by_comment(manifest.contents(), &span, &UNUSED_FEATURE) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("feature `{name}` is unused");
+        let mut message = level.title(&title).snippet(
+            Snippet::source(manifest.contents())
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                UNUSED_FEATURE.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help =
+            format!("remove the feature, or check for it with `#[cfg(feature = \"{name}\")]`");
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Collects the feature names checked for in the sources of `pkg`
+///
+/// This reads every Rust file that would be packaged, which leaves out nested
+/// packages and the target directory. Build scripts can't use `cfg(feature)`,
+/// so they are checked for `CARGO_FEATURE_<name>` instead. Files that can't be
+/// read are skipped.
+fn cfg_features_in_sources(pkg: &Package, gctx: &GlobalContext) -> Option<HashSet<String>> {
+    let source = PathSource::new(pkg.root(), pkg.package_id().source_id(), gctx);
+    let files = source.list_files(pkg).ok()?;
+    let build_scripts = pkg
+        .targets()
+        .iter()
+        .filter(|target| target.is_custom_build())
+        .filter_map(|target| target.src_path().path())
+        .collect::<Vec<_>>();
+    let mut used = HashSet::new();
+    for file in files {
+        if file.extension().map_or(true, |ext| ext != "rs") {
+            continue;
+        }
+        let Ok(contents) = paths::read(&file) else {
+            continue;
+        };
+        if build_scripts.contains(&file.as_path()) {
+            used.extend(build_script_features(
+                &contents,
+                pkg.summary().features().keys(),
+            ));
+        } else {
+            used.extend(cfg_features(&contents));
+        }
+    }
+    Some(used)
+}
+
+/// Finds every `feature = "<name>"` predicate in `contents`
+fn cfg_features(contents: &str) -> impl Iterator<Item = String> + '_ {
+    contents.match_indices("feature").filter_map(|(index, _)| {
+        let rest = contents[index + "feature".len()..].trim_start();
+        let rest = rest.strip_prefix('=')?.trim_start();
+        let rest = rest.strip_prefix('"')?;
+        let end = rest.find('"')?;
+        Some(rest[..end].to_owned())
+    })
+}
+
+/// Finds the features a build script reads through `CARGO_FEATURE_<name>`
+fn build_script_features<'a>(
+    contents: &str,
+    features: impl Iterator<Item = &'a InternedString>,
+) -> Vec<String> {
+    features
+        .filter(|name| {
+            let var = format!("CARGO_FEATURE_{}", name.to_uppercase().replace('-', "_"));
+            contents.contains(&var)
+        })
+        .map(|name| name.to_string())
+        .collect()
+}
+
+const UNUSED_OPTIONAL_DEPENDENCY: Lint = Lint {
+    name: "unused_optional_dependency",
+    desc: "unused optional dependency",
+    groups: &[],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.79",
+    docs: Some(
+        r#"
+### What it does
+Checks for optional dependencies that are not activated by any feature
+
+### Why it is bad
+Starting in the 2024 edition, `cargo` no longer implicitly creates features
+for optional dependencies (see [RFC #3491]). This means that any optional
+dependency not specified with `"dep:<name>"` in some feature is now unused.
+This change may be surprising to users who have been using the implicit
+features `cargo` has been creating for optional dependencies.
+
+### Example
+```toml
+edition = "2024"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+# No explicit feature activation for `bar`
+```
+
+Instead, the dependency should be removed or activated in a feature:
+```toml
+edition = "2024"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+bar = ["dep:bar"]
+```
+
+[RFC #3491]: https://rust-lang.github.io/rfcs/3491-remove-implicit-features.html
+"#,
+    ),
+};
+
+pub fn unused_dependencies(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let edition = manifest.edition();
+    // Unused optional dependencies can only exist on edition 2024+
+    if edition < Edition::Edition2024 {
+        return Ok(());
+    }
+
+    let (lint_level, reason, trace) = UNUSED_OPTIONAL_DEPENDENCY.level_with_trace(
+        pkg_lints,
+        edition,
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    let mut emitted_source = None;
+    let original_toml = manifest.original_toml();
+    // Unused dependencies were stripped from the manifest, leaving only the used ones
+    let used_dependencies = manifest
+        .dependencies()
+        .into_iter()
+        .map(|d| d.name_in_toml().to_string())
+        .collect::<HashSet<String>>();
+    let mut orig_deps = vec![
+        (
+            original_toml.dependencies.as_ref(),
+            vec![DepKind::Normal.kind_table()],
+        ),
+        (
+            original_toml.dev_dependencies.as_ref(),
+            vec![DepKind::Development.kind_table()],
+        ),
+        (
+            original_toml.build_dependencies.as_ref(),
+            vec![DepKind::Build.kind_table()],
+        ),
+    ];
+    for (name, platform) in original_toml.target.iter().flatten() {
+        orig_deps.push((
+            platform.dependencies.as_ref(),
+            vec!["target", name, DepKind::Normal.kind_table()],
+        ));
+        orig_deps.push((
+            platform.dev_dependencies.as_ref(),
+            vec!["target", name, DepKind::Development.kind_table()],
+        ));
+        orig_deps.push((
+            platform.build_dependencies.as_ref(),
+            vec!["target", name, DepKind::Normal.kind_table()],
+        ));
+    }
+    for (deps, toml_path) in orig_deps {
+        if let Some(deps) = deps {
+            for name in deps.keys() {
+                if !used_dependencies.contains(name.as_str()) {
+                    let toml_path = toml_path
+                        .iter()
+                        .map(|s| *s)
+                        .chain(std::iter::once(name.as_str()))
+                        .collect::<Vec<_>>();
+                    let span = get_span(manifest.document(), toml_path.as_slice(), false).unwrap();
+                    if is_allowed_by_comment(
+                        manifest.contents(),
+                        &span,
+                        &UNUSED_OPTIONAL_DEPENDENCY,
+                    ) {
+                        continue;
+                    }
+                    if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                        *error_count += 1;
+                    }
+                    let manifest_path = rel_cwd_manifest_path(path, gctx);
+
+                    let mut message = level.title(UNUSED_OPTIONAL_DEPENDENCY.desc).snippet(
+                        Snippet::source(manifest.contents())
+                            .origin(&manifest_path)
+                            .annotation(level.span(span))
+                            .fold(true),
+                    );
+                    if emitted_source.is_none() {
+                        emitted_source = Some(format!(
+                            "`cargo::{}` is set to `{lint_level}` {reason}",
+                            UNUSED_OPTIONAL_DEPENDENCY.name
+                        ));
+                        message = message
+                            .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                            .footers(trace.iter().map(|note| Level::Note.title(note)));
+                    }
+                    let help = format!(
+                        "remove the dependency or activate it in a feature with `dep:{name}`"
+                    );
+                    message = message.footer(Level::Help.title(&help));
+
+                    gctx.shell().print_message(message)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+const UNUSED_WORKSPACE_PACKAGE_FIELD: Lint = Lint {
+    name: "unused_workspace_package_field",
+    desc: "`[workspace.package]` field is not inherited by any member",
+    groups: &[NURSERY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for fields in `[workspace.package]` that no workspace member inherits
+
+This lint is set in `[workspace.lints.cargo]` of the workspace root manifest.
+
+### Why it is bad
+A field that is never inherited has no effect, so its value can silently
+drift from what the members actually use.
+
+### Example
+```toml
+[workspace]
+members = ["foo"]
+
+[workspace.package]
+version = "0.1.0"
+license = "MIT"
+```
+where `foo` only has `version.workspace = true`.
+"#,
+    ),
+};
+
+pub fn check_unused_workspace_package_fields(
+    ws: &Workspace<'_>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let (contents, document, resolved_ws, unstable_features) = match ws.root_maybe() {
+        MaybePackage::Package(pkg) => {
+            let manifest = pkg.manifest();
+            (
+                manifest.contents(),
+                manifest.document(),
+                manifest.resolved_toml().workspace.as_ref(),
+                manifest.unstable_features(),
+            )
+        }
+        MaybePackage::Virtual(vm) => (
+            vm.contents(),
+            vm.document(),
+            vm.resolved_toml().workspace.as_ref(),
+            vm.unstable_features(),
+        ),
+    };
+    let Some(resolved_ws) = resolved_ws else {
+        return Ok(());
+    };
+    let ws_lints = resolved_ws
+        .lints
+        .as_ref()
+        .and_then(|lints| lints.get("cargo"))
+        .cloned()
+        .unwrap_or_default();
+    let (lint_level, reason, trace) = UNUSED_WORKSPACE_PACKAGE_FIELD.level_with_trace(
+        &ws_lints,
+        Edition::default(),
+        unstable_features,
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let Some(ws_package) = document
+        .as_item()
+        .get("workspace")
+        .and_then(|ws| ws.get("package"))
+        .and_then(|p| p.as_table_like())
+    else {
+        return Ok(());
+    };
+    let inherited = inherited_package_fields(ws);
+
+    let manifest_path = rel_cwd_manifest_path(ws.root_manifest(), gctx);
+    let mut emitted_source = None;
+    for (key, _) in ws_package.iter() {
+        if inherited.contains(key) {
+            continue;
+        }
+        let Some(span) = get_span(document, &["workspace", "package", key], false) else {
+            continue;
+        };
+        if is_allowed_by_comment(contents, &span, &UNUSED_WORKSPACE_PACKAGE_FIELD) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("`workspace.package.{key}` is not inherited by any member");
+        let mut message = level.title(&title).snippet(
+            Snippet::source(contents)
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                UNUSED_WORKSPACE_PACKAGE_FIELD.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        let help = format!("remove the field or inherit it with `{key}.workspace = true`");
+        message = message.footer(Level::Help.title(&help));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+/// Fields a member inherits through `<field>.workspace = true`
+///
+/// `badges` is the one field inherited from a top-level table rather than from
+/// `[package]`.
+fn inherited_package_fields(ws: &Workspace<'_>) -> HashSet<String> {
+    let is_inherited = |value: &toml_edit::Item| {
+        value
+            .as_table_like()
+            .and_then(|t| t.get("workspace"))
+            .and_then(|w| w.as_bool())
+            == Some(true)
+    };
+    ws.members()
+        .flat_map(|member| {
+            let root = member.manifest().document().as_item();
+            let package_fields = root
+                .get("package")
+                .and_then(|p| p.as_table_like())
+                .into_iter()
+                .flat_map(|p| p.iter())
+                .filter(|(_, value)| is_inherited(*value))
+                .map(|(key, _)| key.to_owned());
+            let badges = root
+                .get("badges")
+                .filter(|&badges| is_inherited(badges))
+                .map(|_| "badges".to_owned());
+            package_fields.chain(badges).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+const WILDCARD_DEPENDENCY: Lint = Lint {
+    name: "wildcard_dependency",
+    desc: "dependency has a wildcard version requirement",
+    groups: &[PEDANTIC],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for dependencies with a version requirement of `*`
+
+This can be limited to one kind of dependency with `scope`, like
+`wildcard_dependency = { level = "warn", scope = "normal" }`.
+
+### Why it is bad
+Any version of the dependency is accepted, including ones with breaking
+changes. Packages with wildcard dependencies also cannot be published to
+crates.io.
+
+### Example
+```toml
+[dependencies]
+bar = "*"
+```
+
+Should be written as:
+```toml
+[dependencies]
+bar = "0.1.0"
+```
+"#,
+    ),
+};
+
+pub fn check_wildcard_dependency(
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let manifest = pkg.manifest();
+    let (lint_level, reason, trace) = WILDCARD_DEPENDENCY.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+    let scope = WILDCARD_DEPENDENCY.dependency_scope(pkg_lints)?;
+
+    let document = manifest.document();
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let mut emitted_source = None;
+    for (table_path, deps) in dependency_tables(document) {
+        if scope.is_some_and(|scope| dependency_table_kind(&table_path) != scope) {
+            continue;
+        }
+        for (name, dep) in deps.iter() {
+            let mut toml_path = table_path.clone();
+            toml_path.push(name);
+            let version = match dep.as_table_like() {
+                Some(dep) => {
+                    toml_path.push("version");
+                    dep.get("version").and_then(|v| v.as_str())
+                }
+                None => dep.as_str(),
+            };
+            if version.map(str::trim) != Some("*") {
+                continue;
+            }
+            let Some(span) = get_span(document, &toml_path, true) else {
+                continue;
+            };
+            if is_allowed_by_comment(manifest.contents(), &span, &WILDCARD_DEPENDENCY) {
+                continue;
+            }
+            if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+                *error_count += 1;
+            }
+            let title = format!("dependency `{name}` has a wildcard version requirement");
+            let mut message = level.title(&title).snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            );
+            if emitted_source.is_none() {
+                emitted_source = Some(format!(
+                    "`cargo::{}` is set to `{lint_level}` {reason}",
+                    WILDCARD_DEPENDENCY.name
+                ));
+                message = message
+                    .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                    .footers(trace.iter().map(|note| Level::Note.title(note)));
+            }
+            message = message.footer(Level::Help.title("specify the versions that are supported"));
+
+            gctx.shell().print_message(message)?;
+        }
+    }
+    Ok(())
+}
+
+const WORKSPACE_DEFAULT_MEMBERS_NOT_SUBSET: Lint = Lint {
+    name: "workspace_default_members_not_subset",
+    desc: "default member is not a member of the workspace",
+    groups: &[CORRECTNESS],
+    default_level: LintLevel::Deny,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for `workspace.default-members` entries that are not members of the
+workspace
+
+### Why it is bad
+Default members must be a subset of `workspace.members`. This is only checked
+when running `cargo` from the workspace root, so the mistake can go unnoticed
+when working in a member's directory.
+
+### Example
+```toml
+[workspace]
+members = ["foo"]
+default-members = ["bar"]
+```
+
+Should be written as:
+```toml
+[workspace]
+members = ["foo", "bar"]
+default-members = ["bar"]
+```
+"#,
+    ),
+};
+
+pub fn check_workspace_default_members_not_subset(
+    ws: &Workspace<'_>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let (contents, document, resolved_ws, unstable_features) = match ws.root_maybe() {
+        MaybePackage::Package(pkg) => {
+            let manifest = pkg.manifest();
+            (
+                manifest.contents(),
+                manifest.document(),
+                manifest.resolved_toml().workspace.as_ref(),
+                manifest.unstable_features(),
+            )
+        }
+        MaybePackage::Virtual(vm) => (
+            vm.contents(),
+            vm.document(),
+            vm.resolved_toml().workspace.as_ref(),
+            vm.unstable_features(),
+        ),
+    };
+    let Some(resolved_ws) = resolved_ws else {
+        return Ok(());
+    };
+    let ws_lints = resolved_ws
+        .lints
+        .as_ref()
+        .and_then(|lints| lints.get("cargo"))
+        .cloned()
+        .unwrap_or_default();
+    let (lint_level, reason, trace) = WORKSPACE_DEFAULT_MEMBERS_NOT_SUBSET.level_with_trace(
+        &ws_lints,
+        Edition::default(),
+        unstable_features,
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let manifest_path = rel_cwd_manifest_path(ws.root_manifest(), gctx);
+    let mut emitted_source = None;
+    for default_member in ws.invalid_default_members()? {
+        let Some(span) = get_span(
+            document,
+            &["workspace", "default-members", default_member],
+            true,
+        ) else {
+            continue;
+        };
+        if is_allowed_by_comment(contents, &span, &WORKSPACE_DEFAULT_MEMBERS_NOT_SUBSET) {
+            continue;
+        }
+        if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+            *error_count += 1;
+        }
+        let title = format!("`{default_member}` is listed in default-members but is not a member");
+        let mut message = level.title(&title).snippet(
+            Snippet::source(contents)
+                .origin(&manifest_path)
+                .annotation(level.span(span))
+                .fold(true),
+        );
+        if emitted_source.is_none() {
+            emitted_source = Some(format!(
+                "`cargo::{}` is set to `{lint_level}` {reason}",
+                WORKSPACE_DEFAULT_MEMBERS_NOT_SUBSET.name
+            ));
+            message = message
+                .footer(Level::Note.title(emitted_source.as_ref().unwrap()))
+                .footers(trace.iter().map(|note| Level::Note.title(note)));
+        }
+        message = message.footer(Level::Help.title("add it to `workspace.members` or remove it"));
+
+        gctx.shell().print_message(message)?;
+    }
+    Ok(())
+}
+
+const WORKSPACE_PACKAGE_UNUSED_IN_VIRTUAL_ONLY_WORKSPACE: Lint = Lint {
+    name: "workspace_package_unused_in_virtual_only_workspace",
+    desc: "`[workspace.package]` is not inherited by any member of a virtual workspace",
+    groups: &[NURSERY],
+    default_level: LintLevel::Allow,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for a `[workspace.package]` table in a virtual workspace when no member
+inherits any of its fields
+
+This lint is set in `[workspace.lints.cargo]` of the workspace root manifest.
+
+### Why it is bad
+A virtual workspace has no package of its own, so the whole table has no
+effect. Unlike `unused_workspace_package_field`, this points at the table once
+rather than at each of its fields.
+
+### Example
+```toml
+[workspace]
+members = ["foo"]
+
+[workspace.package]
+version = "0.1.0"
+license = "MIT"
+```
+where `foo` sets `version` and `license` itself.
+"#,
+    ),
+};
+
+pub fn check_workspace_package_unused_in_virtual_only_workspace(
+    ws: &Workspace<'_>,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let MaybePackage::Virtual(vm) = ws.root_maybe() else {
+        return Ok(());
+    };
+    let Some(resolved_ws) = vm.resolved_toml().workspace.as_ref() else {
+        return Ok(());
+    };
+    let ws_lints = resolved_ws
+        .lints
+        .as_ref()
+        .and_then(|lints| lints.get("cargo"))
+        .cloned()
+        .unwrap_or_default();
+    let (lint_level, reason, trace) = WORKSPACE_PACKAGE_UNUSED_IN_VIRTUAL_ONLY_WORKSPACE
+        .level_with_trace(&ws_lints, Edition::default(), vm.unstable_features(), gctx);
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let contents = vm.contents();
+    let document = vm.document();
+    let Some(span) = get_span(document, &["workspace", "package"], false) else {
+        return Ok(());
+    };
+    if !inherited_package_fields(ws).is_empty() {
+        return Ok(());
+    }
+    if is_allowed_by_comment(
+        contents,
+        &span,
+        &WORKSPACE_PACKAGE_UNUSED_IN_VIRTUAL_ONLY_WORKSPACE,
+    ) {
+        return Ok(());
+    }
+    if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+        *error_count += 1;
+    }
+
+    let manifest_path = rel_cwd_manifest_path(ws.root_manifest(), gctx);
+    let emitted_source = format!(
+        "`cargo::{}` is set to `{lint_level}` {reason}",
+        WORKSPACE_PACKAGE_UNUSED_IN_VIRTUAL_ONLY_WORKSPACE.name
+    );
+    let message = level
+        .title("`[workspace.package]` is not inherited by any member")
+        .snippet(lint_snippet(level, contents, &manifest_path, span, []))
+        .footer(Level::Note.title(&emitted_source))
+        .footers(trace.iter().map(|note| Level::Note.title(note)))
+        .footer(
+            Level::Help
+                .title("remove the table or inherit its fields with `<field>.workspace = true`"),
+        );
+
+    gctx.shell().print_message(message)?;
+    Ok(())
+}
+
+const WORKSPACE_RESOLVER_MISMATCH: Lint = Lint {
+    name: "workspace_resolver_mismatch",
+    desc: "workspace member sets a different `resolver` than the workspace",
+    groups: &[SUSPICIOUS],
+    default_level: LintLevel::Warn,
+    edition_lint_opts: &[],
+    feature_gate: None,
+    applicability: Applicability::Unspecified,
+    needs_registry: false,
+    since: "1.82",
+    docs: Some(
+        r#"
+### What it does
+Checks for workspace members whose `package.resolver` differs from the
+resolver the workspace uses
+
+### Why it is bad
+The resolver is a setting of the whole workspace, so the member's `resolver`
+is ignored. Building the member on its own and as part of the workspace may
+resolve features differently than the manifest suggests.
+
+### Example
+```toml
+# Cargo.toml
+[workspace]
+members = ["foo"]
+resolver = "1"
+
+# foo/Cargo.toml
+[package]
+name = "foo"
+version = "0.1.0"
+resolver = "2"
+```
+
+Should be written as:
+```toml
+# Cargo.toml
+[workspace]
+members = ["foo"]
+resolver = "2"
+
+# foo/Cargo.toml
+[package]
+name = "foo"
+version = "0.1.0"
+```
+"#,
+    ),
+};
+
+pub fn check_workspace_resolver_mismatch(
+    ws: &Workspace<'_>,
+    pkg: &Package,
+    path: &Path,
+    pkg_lints: &TomlToolLints,
+    error_count: &mut usize,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    if pkg.manifest_path() == ws.root_manifest() {
+        return Ok(());
+    }
+    let manifest = pkg.manifest();
+    let Some(behavior) = manifest.resolve_behavior() else {
+        return Ok(());
+    };
+    let ws_behavior = ws.resolve_behavior();
+    if behavior == ws_behavior {
+        return Ok(());
+    }
+    let (lint_level, reason, trace) = WORKSPACE_RESOLVER_MISMATCH.level_with_trace(
+        pkg_lints,
+        manifest.edition(),
+        manifest.unstable_features(),
+        gctx,
+    );
+    let trace = level_trace_notes(&trace, gctx);
+    let Some(level) = lint_level.to_diagnostic_level() else {
+        return Ok(());
+    };
+
+    let Some(span) = get_span(manifest.document(), &["package", "resolver"], false) else {
+        return Ok(());
+    };
+    if is_allowed_by_comment(manifest.contents(), &span, &WORKSPACE_RESOLVER_MISMATCH) {
+        return Ok(());
+    }
+    if lint_level == LintLevel::Forbid || lint_level == LintLevel::Deny {
+        *error_count += 1;
+    }
+
+    let (ws_contents, ws_document) = match ws.root_maybe() {
+        MaybePackage::Package(pkg) => (pkg.manifest().contents(), pkg.manifest().document()),
+        MaybePackage::Virtual(vm) => (vm.contents(), vm.document()),
+    };
+    let ws_span = get_span(ws_document, &["workspace", "resolver"], false)
+        .or_else(|| get_span(ws_document, &["package", "resolver"], false));
+    let manifest_path = rel_cwd_manifest_path(path, gctx);
+    let ws_path = rel_cwd_manifest_path(ws.root_manifest(), gctx);
+    let title = format!(
+        "`resolver = \"{}\"` is ignored for workspace members",
+        behavior.to_manifest()
+    );
+    let ws_title = format!(
+        "the workspace uses `resolver = \"{}\"`",
+        ws_behavior.to_manifest()
+    );
+    let ws_note = match ws_span {
+        Some(ws_span) => Level::Note.title(&ws_title).snippet(
+            Snippet::source(ws_contents)
+                .origin(&ws_path)
+                .annotation(Level::Note.span(ws_span))
+                .fold(true),
+        ),
+        None => Level::Note.title(&ws_title),
+    };
+    let emitted_source = format!(
+        "`cargo::{}` is set to `{lint_level}` {reason}",
+        WORKSPACE_RESOLVER_MISMATCH.name
+    );
+    let message =
+        level
+            .title(&title)
+            .snippet(
+                Snippet::source(manifest.contents())
+                    .origin(&manifest_path)
+                    .annotation(level.span(span))
+                    .fold(true),
+            )
+            .footer(ws_note)
+            .footer(Level::Note.title(&emitted_source))
+            .footers(trace.iter().map(|note| Level::Note.title(note)))
+            .footer(Level::Help.title(
+                "remove `resolver` here, or set it in the workspace root's manifest instead",
+            ));
+
+    gctx.shell().print_message(message)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+    use snapbox::ToDebug;
+    use std::collections::HashSet;
+
+    use super::{lint_snippet, Lint, LintGroup, LintLevel, LintLevelReason};
+    use crate::core::dependency::DepKind;
+    use crate::core::{Edition, Features};
+    use annotate_snippets::{Level, Renderer};
+    use cargo_util_schemas::manifest::TomlToolLints;
+
+    #[test]
+    fn lint_level_round_trip() {
+        for level in [
+            LintLevel::Allow,
+            LintLevel::Warn,
+            LintLevel::Deny,
+            LintLevel::Forbid,
+        ] {
+            assert_eq!(level.to_string().parse::<LintLevel>().unwrap(), level);
+            assert_eq!(
+                LintLevel::try_from(level.to_string().as_str()).unwrap(),
+                level
+            );
+        }
+    }
+
+    #[test]
+    fn lint_level_case_insensitive() {
+        assert_eq!("ALLOW".parse::<LintLevel>().unwrap(), LintLevel::Allow);
+        assert_eq!("Warn".parse::<LintLevel>().unwrap(), LintLevel::Warn);
+        assert_eq!("dEnY".parse::<LintLevel>().unwrap(), LintLevel::Deny);
+        assert_eq!("FORBID".parse::<LintLevel>().unwrap(), LintLevel::Forbid);
+    }
+
+    #[test]
+    fn allow_has_no_diagnostic_level() {
+        assert!(LintLevel::Allow.to_diagnostic_level().is_none());
+        assert!(LintLevel::Warn.to_diagnostic_level().is_some());
+        assert!(LintLevel::Deny.to_diagnostic_level().is_some());
+        assert!(LintLevel::Forbid.to_diagnostic_level().is_some());
+    }
+
+    #[test]
+    fn edition_lint_opts_escalate() {
+        let lint = Lint {
+            name: "escalating",
+            desc: "escalates across editions",
+            groups: &[],
+            default_level: LintLevel::Allow,
+            edition_lint_opts: &[
+                (Edition::Edition2024, LintLevel::Deny),
+                (Edition::Edition2018, LintLevel::Allow),
+                (Edition::Edition2021, LintLevel::Warn),
+            ],
+            feature_gate: None,
+            applicability: Applicability::Unspecified,
+            needs_registry: false,
+            since: "1.82",
+            docs: None,
+        };
+        let pkg_lints = TomlToolLints::default();
+        let features = Features::default();
+
+        let level = |edition| lint.level(&pkg_lints, edition, &features);
+        assert_eq!(
+            level(Edition::Edition2015),
+            (LintLevel::Allow, LintLevelReason::Default)
+        );
+        assert_eq!(
+            level(Edition::Edition2018),
+            (
+                LintLevel::Allow,
+                LintLevelReason::Edition(Edition::Edition2018)
+            )
+        );
+        assert_eq!(
+            level(Edition::Edition2021),
+            (
+                LintLevel::Warn,
+                LintLevelReason::Edition(Edition::Edition2021)
+            )
+        );
+        assert_eq!(
+            level(Edition::Edition2024),
+            (
+                LintLevel::Deny,
+                LintLevelReason::Edition(Edition::Edition2024)
+            )
+        );
+    }
+
+    /// Builds a lint in a single group, for checking how [`Lint::level`]
+    /// chooses between the two
+    fn harness_lint(
+        default_level: LintLevel,
+        edition_lint_opts: &'static [(Edition, LintLevel)],
+    ) -> Lint {
+        Lint {
+            name: "zzz_lint",
+            desc: "a lint for testing level precedence",
+            // Named so that it sorts before the lint, to make sure ties are
+            // not decided by name
+            groups: &[LintGroup {
+                name: "aaa_group",
+                desc: "a group for testing level precedence",
+                default_level: LintLevel::Allow,
+                edition_lint_opts: &[],
+                feature_gate: None,
+            }],
+            default_level,
+            edition_lint_opts,
+            feature_gate: None,
+            applicability: Applicability::Unspecified,
+            needs_registry: false,
+            since: "1.82",
+            docs: None,
+        }
+    }
+
+    /// The level of `lint` in a package on `edition` with `pkg_lints` as its
+    /// `[lints.cargo]` table
+    fn harness_level(
+        lint: Lint,
+        pkg_lints: &str,
+        edition: Edition,
+    ) -> (LintLevel, LintLevelReason) {
+        let pkg_lints: TomlToolLints = toml::from_str(pkg_lints).unwrap();
+        lint.level(&pkg_lints, edition, &Features::default())
+    }
+
+    fn priority_lint(pkg_lints: &str) -> (LintLevel, LintLevelReason) {
+        harness_level(
+            harness_lint(LintLevel::Allow, &[]),
+            pkg_lints,
+            Edition::Edition2021,
+        )
+    }
+
+    #[test]
+    fn precedence_forbid_from_lint() {
+        let level = harness_level(
+            harness_lint(LintLevel::Allow, &[]),
+            r#"
+zzz_lint = { level = "forbid", priority = -5 }
+aaa_group = { level = "allow", priority = 5 }
+"#,
+            Edition::Edition2021,
+        );
+        assert_eq!(level, (LintLevel::Forbid, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn precedence_forbid_from_group() {
+        let level = harness_level(
+            harness_lint(LintLevel::Allow, &[]),
+            r#"
+zzz_lint = { level = "allow", priority = 5 }
+aaa_group = { level = "forbid", priority = -5 }
+"#,
+            Edition::Edition2021,
+        );
+        assert_eq!(level, (LintLevel::Forbid, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn precedence_forbid_from_edition() {
+        let lint = harness_lint(
+            LintLevel::Allow,
+            &[(Edition::Edition2021, LintLevel::Forbid)],
+        );
+        let level = harness_level(lint, r#"zzz_lint = "allow""#, Edition::Edition2021);
+        assert_eq!(
+            level,
+            (
+                LintLevel::Forbid,
+                LintLevelReason::Edition(Edition::Edition2021)
+            )
+        );
+
+        // Before the edition, it can be overridden like any other level
+        let level = harness_level(lint, r#"zzz_lint = "allow""#, Edition::Edition2018);
+        assert_eq!(level, (LintLevel::Allow, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn precedence_forbid_from_default() {
+        let level = harness_level(
+            harness_lint(LintLevel::Forbid, &[]),
+            r#"
+zzz_lint = "allow"
+aaa_group = { level = "warn", priority = 5 }
+"#,
+            Edition::Edition2021,
+        );
+        assert_eq!(level, (LintLevel::Forbid, LintLevelReason::Default));
+    }
+
+    #[test]
+    fn precedence_lint_only() {
+        let level = harness_level(
+            harness_lint(LintLevel::Allow, &[]),
+            r#"zzz_lint = "deny""#,
+            Edition::Edition2021,
+        );
+        assert_eq!(level, (LintLevel::Deny, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn precedence_group_only() {
+        let level = harness_level(
+            harness_lint(LintLevel::Allow, &[]),
+            r#"aaa_group = "warn""#,
+            Edition::Edition2021,
+        );
+        assert_eq!(level, (LintLevel::Warn, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn precedence_edition_only() {
+        let level = harness_level(
+            harness_lint(LintLevel::Allow, &[(Edition::Edition2021, LintLevel::Warn)]),
+            "",
+            Edition::Edition2021,
+        );
+        assert_eq!(
+            level,
+            (
+                LintLevel::Warn,
+                LintLevelReason::Edition(Edition::Edition2021)
+            )
+        );
+    }
+
+    #[test]
+    fn precedence_default() {
+        let level = harness_level(harness_lint(LintLevel::Warn, &[]), "", Edition::Edition2021);
+        assert_eq!(level, (LintLevel::Warn, LintLevelReason::Default));
+    }
+
+    #[test]
+    fn lint_priority_lower_than_group() {
+        let level = priority_lint(
+            r#"
+zzz_lint = { level = "warn", priority = -1 }
+aaa_group = { level = "deny", priority = 0 }
+"#,
+        );
+        assert_eq!(level, (LintLevel::Deny, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn lint_priority_equal_to_group() {
+        let level = priority_lint(
+            r#"
+zzz_lint = { level = "warn", priority = 2 }
+aaa_group = { level = "deny", priority = 2 }
+"#,
+        );
+        assert_eq!(level, (LintLevel::Warn, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn group_priority_equal_to_default_lint() {
+        let level = priority_lint(r#"aaa_group = { level = "deny", priority = 0 }"#);
+        assert_eq!(level, (LintLevel::Deny, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn lint_priority_higher_than_group() {
+        let level = priority_lint(
+            r#"
+zzz_lint = { level = "warn", priority = 5 }
+aaa_group = { level = "deny", priority = 1 }
+"#,
+        );
+        assert_eq!(level, (LintLevel::Warn, LintLevelReason::Package));
+
+        let level = priority_lint(
+            r#"
+zzz_lint = { level = "warn", priority = 1 }
+aaa_group = { level = "deny", priority = 5 }
+"#,
+        );
+        assert_eq!(level, (LintLevel::Deny, LintLevelReason::Package));
+    }
+
+    fn warnings_lint(pkg_lints: &str) -> (LintLevel, LintLevelReason) {
+        let lint = Lint {
+            name: "zzz_lint",
+            desc: "a lint for testing the `warnings` group",
+            groups: &[],
+            default_level: LintLevel::Warn,
+            edition_lint_opts: &[],
+            feature_gate: None,
+            applicability: Applicability::Unspecified,
+            needs_registry: false,
+            since: "1.82",
+            docs: None,
+        };
+        let pkg_lints: TomlToolLints = toml::from_str(pkg_lints).unwrap();
+        lint.level(&pkg_lints, Edition::Edition2021, &Features::default())
+    }
+
+    #[test]
+    fn warnings_escalates_warn_lint() {
+        let level = warnings_lint(r#"warnings = "deny""#);
+        assert_eq!(level, (LintLevel::Deny, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn warnings_ignores_non_warn_lint() {
+        let level = warnings_lint(
+            r#"
+zzz_lint = "allow"
+warnings = "deny"
+"#,
+        );
+        assert_eq!(level, (LintLevel::Allow, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn specific_lint_wins_over_warnings() {
+        let level = warnings_lint(
+            r#"
+zzz_lint = "warn"
+warnings = { level = "deny", priority = 10 }
+"#,
+        );
+        assert_eq!(level, (LintLevel::Warn, LintLevelReason::Package));
+    }
+
+    #[test]
+    fn dependency_scope() {
+        let scope = |pkg_lints: &str| {
+            let pkg_lints: TomlToolLints = toml::from_str(pkg_lints).unwrap();
+            super::WILDCARD_DEPENDENCY.dependency_scope(&pkg_lints)
+        };
+        assert_eq!(scope(r#"wildcard_dependency = "deny""#).unwrap(), None);
+        assert_eq!(
+            scope(r#"wildcard_dependency = { level = "deny" }"#).unwrap(),
+            None
+        );
+        assert_eq!(
+            scope(r#"wildcard_dependency = { level = "deny", scope = "dev" }"#).unwrap(),
+            Some(DepKind::Development)
+        );
+        assert!(scope(r#"wildcard_dependency = { level = "deny", scope = 1 }"#).is_err());
+    }
+
+    #[test]
+    fn parent_dir_depth() {
+        use super::parent_dir_depth;
+        use std::path::Path;
+
+        assert_eq!(parent_dir_depth(Path::new("bar")), Some(0));
+        assert_eq!(parent_dir_depth(Path::new("../bar")), Some(1));
+        assert_eq!(parent_dir_depth(Path::new("./../../bar")), Some(2));
+        assert_eq!(parent_dir_depth(Path::new("a/../../b/../../bar")), Some(2));
+        assert_eq!(parent_dir_depth(Path::new("../a/b/../../bar")), Some(1));
+        assert_eq!(parent_dir_depth(Path::new("/bar")), None);
+    }
+
+    #[test]
+    fn feature_gated_lint() {
+        assert!(super::IM_A_TEAPOT.is_feature_gated());
+        assert_eq!(
+            super::IM_A_TEAPOT.feature_name(),
+            Some("test_dummy_unstable")
+        );
+    }
+
+    #[test]
+    fn ungated_lint() {
+        assert!(!super::UNKNOWN_LINTS.is_feature_gated());
+        assert_eq!(super::UNKNOWN_LINTS.feature_name(), None);
+    }
+
+    #[test]
+    fn lint_level_invalid() {
+        let err = "error".parse::<LintLevel>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "supported lint levels are `allow`, `warn`, `deny`, or `forbid`, but `error` is unknown"
+        );
+    }
+
+    #[test]
+    fn lint_snippet_multiple_spans() {
+        let source = "[dependencies]\nbar = \"0.1.0\"\nbar = \"0.2.0\"\n";
+        let message = Level::Warning
+            .title("duplicate key `bar`")
+            .snippet(lint_snippet(
+                Level::Warning,
+                source,
+                "Cargo.toml",
+                29..32,
+                [(15..18, "first declared here")],
+            ));
+        let rendered = Renderer::plain().render(message).to_string();
+        assert_eq!(
+            rendered,
+            "\
+warning: duplicate key `bar`
+ --> Cargo.toml:3:1
+  |
+2 | bar = \"0.1.0\"
+  | --- first declared here
+3 | bar = \"0.2.0\"
+  | ---
+  |"
+        );
+    }
+
+    #[test]
+    fn lint_snippet_multiple_spans_on_one_line() {
+        let source = "[dependencies]\nbar = { path = \"bar\", registry = \"alternative\" }\n";
+        let message = Level::Error
+            .title("`registry` is ignored for dependency `bar`")
+            .snippet(lint_snippet(
+                Level::Error,
+                source,
+                "Cargo.toml",
+                37..45,
+                [(23..27, "`cargo` uses this source")],
+            ));
+        let rendered = Renderer::plain().render(message).to_string();
+        assert_eq!(
+            rendered,
+            "\
+error: `registry` is ignored for dependency `bar`
+ --> Cargo.toml:2:23
+  |
+2 | bar = { path = \"bar\", registry = \"alternative\" }
+  |         ----          ^^^^^^^^
+  |         |
+  |         `cargo` uses this source
+  |"
+        );
+    }
 
     #[test]
     fn ensure_sorted_lints() {
@@ -976,6 +7390,7 @@ mod tests {
             .collect::<HashSet<_>>();
         let actual = super::LINT_GROUPS
             .iter()
+            .chain(super::DYNAMIC_LINT_GROUPS)
             .map(|l| l.name.to_uppercase())
             .collect::<HashSet<_>>();
         let diff = expected.difference(&actual).sorted().collect::<Vec<_>>();
@@ -1,3 +1,4 @@
+use std::ops::Range;
 use std::path::{Path, PathBuf};
 
 use cargo_util_schemas::core::PackageIdSpec;
@@ -7,6 +8,7 @@ use serde_json::{json, value::RawValue};
 
 use crate::core::compiler::CompileMode;
 use crate::core::Target;
+use crate::util::lints::Applicability;
 
 pub trait Message: ser::Serialize {
     fn reason(&self) -> &str;
@@ -113,3 +115,34 @@ impl Message for BuildFinished {
         "build-finished"
     }
 }
+
+#[derive(Serialize)]
+pub struct ManifestLint<'a> {
+    pub manifest_path: PathBuf,
+    pub name: &'a str,
+    pub level: String,
+    pub message: &'a str,
+    /// Byte range of what the lint points at within `manifest_path`, if
+    /// anything
+    pub span: Option<Range<usize>>,
+    pub applicability: Applicability,
+    /// The version of Cargo the lint was added in
+    pub since: &'a str,
+    /// A known lint or group the user may have meant instead
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<LintSuggestion<'a>>,
+}
+
+impl<'a> Message for ManifestLint<'a> {
+    fn reason(&self) -> &str {
+        "cargo-manifest-lint"
+    }
+}
+
+#[derive(Serialize)]
+pub struct LintSuggestion<'a> {
+    pub name: &'a str,
+    /// Either `lint` or `group`
+    pub kind: &'a str,
+    pub applicability: Applicability,
+}
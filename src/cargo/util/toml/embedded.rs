@@ -1,14 +1,26 @@
 use anyhow::Context as _;
 
+use annotate_snippets::{Level, Snippet};
 use cargo_util_schemas::manifest::PackageName;
 
+use crate::core::features::{Edition, Feature};
+use crate::util::lints::rel_cwd_manifest_path;
 use crate::util::restricted_names;
+use crate::AlreadyPrintedError;
 use crate::CargoResult;
 use crate::GlobalContext;
 
-const DEFAULT_EDITION: crate::core::features::Edition =
-    crate::core::features::Edition::LATEST_STABLE;
+const DEFAULT_EDITION: Edition = Edition::LATEST_STABLE;
 const AUTO_FIELDS: &[&str] = &["autobins", "autoexamples", "autotests", "autobenches"];
+/// Top-level keys that are not allowed in embedded manifests
+pub const DISALLOWED_KEYS: &[&str] = &["workspace", "lib", "bin", "example", "test", "bench"];
+/// `[package]` keys that are not allowed in embedded manifests, besides [`AUTO_FIELDS`]
+const DISALLOWED_PACKAGE_KEYS: &[&str] = &["workspace", "build", "links"];
+
+/// `[package]` keys that are not allowed in embedded manifests
+pub fn disallowed_package_keys() -> impl Iterator<Item = &'static str> {
+    DISALLOWED_PACKAGE_KEYS.iter().chain(AUTO_FIELDS).copied()
+}
 
 pub(super) fn expand_manifest(
     content: &str,
@@ -28,6 +40,8 @@ pub(super) fn expand_manifest(
             }
         }
 
+        validate_frontmatter(content, frontmatter, path, gctx)?;
+
         // HACK: until rustc has native support for this syntax, we have to remove it from the
         // source file
         use std::fmt::Write as _;
@@ -58,45 +72,109 @@ pub(super) fn expand_manifest(
         }
         cargo_util::paths::write_if_changed(&hacked_path, hacked_source)?;
 
-        let manifest = expand_manifest_(&frontmatter, &hacked_path, gctx)
+        let manifest = expand_manifest_(&frontmatter, &hacked_path, path, gctx)
             .with_context(|| format!("failed to parse manifest at {}", path.display()))?;
         let manifest = toml::to_string_pretty(&manifest)?;
         Ok(manifest)
     } else {
         let frontmatter = "";
-        let manifest = expand_manifest_(frontmatter, path, gctx)
+        let manifest = expand_manifest_(frontmatter, path, path, gctx)
             .with_context(|| format!("failed to parse manifest at {}", path.display()))?;
         let manifest = toml::to_string_pretty(&manifest)?;
         Ok(manifest)
     }
 }
 
+/// Parse the frontmatter on its own so syntax errors can point into the script itself, rather
+/// than into the manifest reconstructed from it
+fn validate_frontmatter(
+    content: &str,
+    frontmatter: &str,
+    path: &std::path::Path,
+    gctx: &GlobalContext,
+) -> CargoResult<()> {
+    let Err(e) = toml::from_str::<toml::Table>(frontmatter) else {
+        return Ok(());
+    };
+    let Some(span) = e.span() else {
+        return Err(e.into());
+    };
+
+    // `frontmatter` is a slice of `content`
+    let offset = frontmatter.as_ptr() as usize - content.as_ptr() as usize;
+    let span = (span.start + offset)..(span.end + offset);
+    let script_path = rel_cwd_manifest_path(path, gctx);
+    let message = Level::Error.title(e.message()).snippet(
+        Snippet::source(content)
+            .origin(&script_path)
+            .fold(true)
+            .annotation(Level::Error.span(span)),
+    );
+    gctx.shell().print_message(message)?;
+    Err(AlreadyPrintedError::new(e.into()).into())
+}
+
 fn expand_manifest_(
     manifest: &str,
     path: &std::path::Path,
+    script_path: &std::path::Path,
     gctx: &GlobalContext,
 ) -> CargoResult<toml::Table> {
     let mut manifest: toml::Table = toml::from_str(&manifest)?;
 
-    for key in ["workspace", "lib", "bin", "example", "test", "bench"] {
-        if manifest.contains_key(key) {
+    for key in DISALLOWED_KEYS {
+        if manifest.contains_key(*key) {
             anyhow::bail!("`{key}` is not allowed in embedded manifests")
         }
     }
 
-    // Prevent looking for a workspace by `read_manifest_from_str`
-    manifest.insert("workspace".to_owned(), toml::Table::new().into());
+    // Applied as config when running the script, see `env_config`
+    if let Some(env) = manifest.remove("env") {
+        if !env.is_table() {
+            anyhow::bail!("`env` must be a table");
+        }
+    }
+
+    let use_default_deps = match manifest.remove("script") {
+        Some(toml::Value::Table(script)) => match script.get("default-dependencies") {
+            Some(toml::Value::Boolean(enabled)) => *enabled,
+            Some(_) => anyhow::bail!("`script.default-dependencies` must be a boolean"),
+            None => true,
+        },
+        Some(_) => anyhow::bail!("`script` must be a table"),
+        None => true,
+    };
+    if use_default_deps {
+        if let Some(default_deps) =
+            gctx.get::<Option<toml::Table>>("script.default-dependencies")?
+        {
+            let deps = manifest
+                .entry("dependencies".to_owned())
+                .or_insert_with(|| toml::Table::new().into())
+                .as_table_mut()
+                .ok_or_else(|| anyhow::format_err!("`dependencies` must be a table"))?;
+            for (name, dep) in default_deps {
+                deps.entry(name).or_insert(dep);
+            }
+        }
+    }
+
+    // Prevent looking for a workspace by `read_manifest_from_str`, unless the script should join
+    // the workspace it resides in
+    if !gctx
+        .get::<Option<bool>>("script.join-workspace")?
+        .unwrap_or(false)
+    {
+        manifest.insert("workspace".to_owned(), toml::Table::new().into());
+    }
 
     let package = manifest
         .entry("package".to_owned())
         .or_insert_with(|| toml::Table::new().into())
         .as_table_mut()
         .ok_or_else(|| anyhow::format_err!("`package` must be a table"))?;
-    for key in ["workspace", "build", "links"]
-        .iter()
-        .chain(AUTO_FIELDS.iter())
-    {
-        if package.contains_key(*key) {
+    for key in disallowed_package_keys() {
+        if package.contains_key(key) {
             anyhow::bail!("`package.{key}` is not allowed in embedded manifests")
         }
     }
@@ -107,17 +185,41 @@ fn expand_manifest_(
         .ok_or_else(|| anyhow::format_err!("no file name"))?
         .to_string_lossy();
     let name = sanitize_name(file_stem.as_ref());
-    let bin_name = name.clone();
-    package
+    // Keep the `[[bin]]` in sync with a user-provided `package.name`
+    let bin_name = package
         .entry("name".to_owned())
-        .or_insert(toml::Value::String(name));
-    package.entry("edition".to_owned()).or_insert_with(|| {
-        let _ = gctx.shell().warn(format_args!(
-            "`package.edition` is unspecified, defaulting to `{}`",
-            DEFAULT_EDITION
-        ));
-        toml::Value::String(DEFAULT_EDITION.to_string())
-    });
+        .or_insert(toml::Value::String(name))
+        .as_str()
+        .ok_or_else(|| anyhow::format_err!("`package.name` must be a string"))?
+        .to_owned();
+    let mut defaulted_edition = None;
+    if !package.contains_key("edition") {
+        // A configured edition is a deliberate choice, so only warn about ours
+        let edition = match gctx.get::<Option<String>>("script.default-edition")? {
+            Some(edition) => edition
+                .parse::<Edition>()
+                .context("failed to parse `script.default-edition`")?,
+            None => {
+                let script_path =
+                    if script_path.starts_with(super::stdin_script_dir(gctx).as_path_unlocked()) {
+                        "<stdin>".to_owned()
+                    } else {
+                        rel_cwd_manifest_path(script_path, gctx)
+                    };
+                let _ = gctx.shell().warn(format_args!(
+                    "`package.edition` is unspecified in `{script_path}`, defaulting to `{}`; \
+                     set `package.edition` in the embedded manifest to silence this warning",
+                    DEFAULT_EDITION
+                ));
+                DEFAULT_EDITION
+            }
+        };
+        package.insert(
+            "edition".to_owned(),
+            toml::Value::String(edition.to_string()),
+        );
+        defaulted_edition = Some(edition);
+    }
     package
         .entry("build".to_owned())
         .or_insert_with(|| toml::Value::Boolean(false));
@@ -135,6 +237,47 @@ fn expand_manifest_(
         toml::Value::Array(vec![toml::Value::Table(bin)]),
     );
 
+    if let Some(edition) = defaulted_edition {
+        add_edition_cargo_feature(&mut manifest, edition)?;
+    }
+    add_bin_profile_defaults(&mut manifest)?;
+
+    Ok(manifest)
+}
+
+/// Enables the `cargo-features` entry `edition` needs if it is still unstable
+///
+/// The user didn't pick the edition, so they shouldn't have to know it is gated.
+fn add_edition_cargo_feature(manifest: &mut toml::Table, edition: Edition) -> CargoResult<()> {
+    if edition.is_stable() {
+        return Ok(());
+    }
+    // Add these lines if start a new unstable edition, like in `to_real_manifest`
+    let feature = match edition {
+        Edition::Edition2024 => Feature::edition2024(),
+        _ => anyhow::bail!(
+            "the default edition {edition} is unstable; \
+             set `package.edition` in the embedded manifest"
+        ),
+    };
+    let features = manifest
+        .entry("cargo-features".to_owned())
+        .or_insert_with(|| toml::Value::Array(Vec::new()))
+        .as_array_mut()
+        .ok_or_else(|| anyhow::format_err!("`cargo-features` must be an array"))?;
+    let name = feature.name();
+    if !features.iter().any(|f| f.as_str() == Some(name)) {
+        features.push(toml::Value::String(name.to_owned()));
+    }
+    Ok(())
+}
+
+/// Profile defaults that only make sense when a `[[bin]]` is built
+fn add_bin_profile_defaults(manifest: &mut toml::Table) -> CargoResult<()> {
+    if !manifest.contains_key("bin") {
+        return Ok(());
+    }
+
     let release = manifest
         .entry("profile".to_owned())
         .or_insert_with(|| toml::Value::Table(Default::default()))
@@ -148,7 +291,182 @@ fn expand_manifest_(
         .entry("strip".to_owned())
         .or_insert_with(|| toml::Value::Boolean(true));
 
-    Ok(manifest)
+    Ok(())
+}
+
+/// Translate the `[env]` table of an embedded manifest into `--config` values, so it is applied
+/// like `[env]` in `.cargo/config.toml`
+pub(super) fn env_config(content: &str) -> CargoResult<Vec<String>> {
+    let source = split_source(content)?;
+    let Some(frontmatter) = source.frontmatter else {
+        return Ok(Vec::new());
+    };
+    let mut manifest: toml::Table = toml::from_str(frontmatter)?;
+    let Some(env) = manifest.remove("env") else {
+        return Ok(Vec::new());
+    };
+    let toml::Value::Table(env) = env else {
+        anyhow::bail!("`env` must be a table");
+    };
+    let config = env
+        .into_iter()
+        .map(|(key, value)| format!("env.{}={value}", toml_edit::Key::new(key)))
+        .collect();
+    Ok(config)
+}
+
+/// The Rust source of a script, with the shebang and frontmatter removed
+pub(super) fn rust_source(content: &str) -> CargoResult<&str> {
+    let source = split_source(content)?;
+    Ok(source.content)
+}
+
+/// The files of a regular package converted from a script
+pub struct ExtractedPackage {
+    /// `Cargo.toml`
+    pub manifest: String,
+    /// `src/main.rs`
+    pub main: String,
+    /// `.cargo/config.toml`, holding the script's `[env]` table, if it had one
+    pub config: Option<String>,
+}
+
+/// Convert a script into a regular package with the default layout
+///
+/// The manifest is expanded like when running the script, except for what only exists to build
+/// the script in place: the `[workspace]` isolating it, the `[[bin]]` pointing at it, and the
+/// `package` keys disabling build scripts and target auto-discovery.
+pub(super) fn extract_package(
+    content: &str,
+    path: &std::path::Path,
+    gctx: &GlobalContext,
+) -> CargoResult<ExtractedPackage> {
+    let source = split_source(content)?;
+    let frontmatter = source.frontmatter.unwrap_or("");
+    if let Some(frontmatter) = source.frontmatter {
+        validate_frontmatter(content, frontmatter, path, gctx)?;
+    }
+
+    let mut manifest = expand_manifest_(frontmatter, path, path, gctx)
+        .with_context(|| format!("failed to parse manifest at {}", path.display()))?;
+    // These are disallowed in embedded manifests, so they were all filled in for the script
+    manifest.remove("workspace");
+    manifest.remove("bin");
+    if let Some(package) = manifest.get_mut("package").and_then(|p| p.as_table_mut()) {
+        package.remove("build");
+        for field in AUTO_FIELDS {
+            package.remove(*field);
+        }
+    }
+
+    let config = match toml::from_str::<toml::Table>(frontmatter)?.remove("env") {
+        Some(env) => {
+            let mut config = toml::Table::new();
+            config.insert("env".to_owned(), env);
+            Some(toml::to_string_pretty(&config)?)
+        }
+        None => None,
+    };
+
+    Ok(ExtractedPackage {
+        manifest: toml::to_string_pretty(&manifest)?,
+        main: source.content.to_owned(),
+        config,
+    })
+}
+
+pub(super) fn edition_is_set(content: &str) -> CargoResult<bool> {
+    let source = split_source(content)?;
+    let manifest: toml::Table = match source.frontmatter {
+        Some(frontmatter) => toml::from_str(frontmatter)?,
+        None => Default::default(),
+    };
+    Ok(manifest
+        .get("package")
+        .and_then(|package| package.get("edition"))
+        .is_some())
+}
+
+/// Crates that are always available without being declared as dependencies
+const BUILTIN_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// Names of crates the script's top-level `use` and `extern crate` items refer
+/// to that are not declared in its embedded `[dependencies]`
+///
+/// This is a best-effort scan of the source, not a parse, so the names are only
+/// candidates.
+pub(super) fn undeclared_crates(content: &str) -> CargoResult<Vec<String>> {
+    let source = split_source(content)?;
+    let manifest: toml::Table = match source.frontmatter {
+        Some(frontmatter) => toml::from_str(frontmatter)?,
+        None => Default::default(),
+    };
+    let mut dep_tables = vec![manifest.get("dependencies")];
+    if let Some(targets) = manifest.get("target").and_then(|t| t.as_table()) {
+        dep_tables.extend(targets.values().map(|target| target.get("dependencies")));
+    }
+    let declared: std::collections::HashSet<_> = dep_tables
+        .into_iter()
+        .flatten()
+        .filter_map(|deps| deps.as_table())
+        .flat_map(|deps| deps.keys())
+        .map(|name| name.replace('-', "_"))
+        .collect();
+
+    let mut local_items = std::collections::HashSet::new();
+    let mut used = Vec::new();
+    for line in source.content.lines() {
+        // Only items at the top level start without indentation
+        let mut item = line;
+        if let Some(rest) = item.strip_prefix("pub") {
+            item = match rest.strip_prefix('(') {
+                Some(rest) => rest.split_once(')').map_or("", |(_, rest)| rest),
+                None => rest,
+            };
+            item = item.trim_start();
+        }
+        let Some((keyword, rest)) = item.split_once(' ') else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        let (rest, is_use) = match keyword {
+            "use" => (rest.strip_prefix("::").unwrap_or(rest), true),
+            "extern" => match rest.strip_prefix("crate ") {
+                Some(rest) => (rest.trim_start(), true),
+                None => continue,
+            },
+            "mod" | "struct" | "enum" | "union" | "trait" | "type" | "fn" | "const" | "static" => {
+                (rest, false)
+            }
+            _ => continue,
+        };
+        let name_end = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        if name.is_empty() {
+            continue;
+        }
+        if is_use {
+            used.push(name);
+        } else {
+            local_items.insert(name);
+        }
+    }
+
+    let mut undeclared: Vec<String> = Vec::new();
+    for name in used {
+        if BUILTIN_CRATES.contains(&name)
+            || ["crate", "self", "super"].contains(&name)
+            || declared.contains(name)
+            || local_items.contains(name)
+            || undeclared.iter().any(|n| n == name)
+        {
+            continue;
+        }
+        undeclared.push(name.to_owned());
+    }
+    Ok(undeclared)
 }
 
 /// Ensure the package name matches the validation from `ops::cargo_new::check_name`
@@ -161,6 +479,7 @@ fn sanitize_name(name: &str) -> String {
         '-'
     };
 
+    let name = transliterate(name);
     let mut name = PackageName::sanitize(name, placeholder).into_inner();
 
     loop {
@@ -182,6 +501,57 @@ fn sanitize_name(name: &str) -> String {
     name
 }
 
+/// Replace accented Latin characters with their ASCII base, so `café` becomes `cafe` rather than
+/// depending on how the rest of sanitization treats non-ASCII characters
+fn transliterate(name: &str) -> String {
+    let mut ascii = String::with_capacity(name.len());
+    for ch in name.chars() {
+        let base = match ch {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ă' | 'ą' => "a",
+            'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+            'æ' => "ae",
+            'Æ' => "AE",
+            'ç' | 'ć' | 'č' => "c",
+            'Ç' | 'Ć' | 'Č' => "C",
+            'ď' | 'đ' => "d",
+            'Ď' | 'Đ' => "D",
+            'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => "e",
+            'È' | 'É' | 'Ê' | 'Ë' | 'Ē' | 'Ę' | 'Ě' => "E",
+            'ğ' => "g",
+            'Ğ' => "G",
+            'ì' | 'í' | 'î' | 'ï' | 'ī' | 'ı' => "i",
+            'Ì' | 'Í' | 'Î' | 'Ï' | 'Ī' | 'İ' => "I",
+            'ł' => "l",
+            'Ł' => "L",
+            'ñ' | 'ń' | 'ň' => "n",
+            'Ñ' | 'Ń' | 'Ň' => "N",
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => "o",
+            'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' | 'Ō' | 'Ő' => "O",
+            'œ' => "oe",
+            'Œ' => "OE",
+            'ř' => "r",
+            'Ř' => "R",
+            'ś' | 'š' | 'ş' => "s",
+            'Ś' | 'Š' | 'Ş' => "S",
+            'ß' => "ss",
+            'ť' | 'ţ' => "t",
+            'Ť' | 'Ţ' => "T",
+            'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => "u",
+            'Ù' | 'Ú' | 'Û' | 'Ü' | 'Ū' | 'Ů' | 'Ű' => "U",
+            'ý' | 'ÿ' => "y",
+            'Ý' | 'Ÿ' => "Y",
+            'ź' | 'ż' | 'ž' => "z",
+            'Ź' | 'Ż' | 'Ž' => "Z",
+            _ => {
+                ascii.push(ch);
+                continue;
+            }
+        };
+        ascii.push_str(base);
+    }
+    ascii
+}
+
 struct Source<'s> {
     shebang: Option<&'s str>,
     info: Option<&'s str>,
@@ -259,6 +629,203 @@ fn split_source(input: &str) -> CargoResult<Source<'_>> {
     Ok(source)
 }
 
+#[cfg(test)]
+mod test_split_source {
+    use super::*;
+
+    #[test]
+    fn plain_shebang() {
+        let source = split_source("#!/usr/bin/env cargo\nfn main() {}\n").unwrap();
+        assert_eq!(source.shebang, Some("#!/usr/bin/env cargo"));
+        assert_eq!(source.content, "fn main() {}\n");
+    }
+
+    #[test]
+    fn split_string_shebang() {
+        let source =
+            split_source("#!/usr/bin/env -S cargo +nightly -Zscript\nfn main() {}\n").unwrap();
+        assert_eq!(
+            source.shebang,
+            Some("#!/usr/bin/env -S cargo +nightly -Zscript")
+        );
+        assert_eq!(source.content, "fn main() {}\n");
+    }
+
+    #[test]
+    fn inner_attribute_is_not_a_shebang() {
+        let source = split_source("#![allow(unused)]\nfn main() {}\n").unwrap();
+        assert_eq!(source.shebang, None);
+        assert_eq!(source.content, "#![allow(unused)]\nfn main() {}\n");
+    }
+}
+
+#[cfg(test)]
+mod test_sanitize_name {
+    use super::*;
+
+    #[test]
+    fn accented_latin() {
+        assert_eq!(sanitize_name("café"), "cafe");
+        assert_eq!(sanitize_name("Straße"), "Strasse");
+    }
+
+    #[test]
+    fn emoji() {
+        assert_eq!(sanitize_name("🚀"), "package");
+        assert_eq!(sanitize_name("🚀launch"), "launch");
+        assert_eq!(sanitize_name("launch🚀"), "launch-");
+    }
+
+    #[test]
+    fn cjk() {
+        assert_eq!(sanitize_name("脚本"), "脚本");
+    }
+
+    #[test]
+    fn guards_still_apply() {
+        assert_eq!(sanitize_name("1café"), "cafe");
+        assert_eq!(sanitize_name("fn"), "fn-");
+    }
+}
+
+#[cfg(test)]
+mod test_undeclared_crates {
+    use super::*;
+
+    #[test]
+    fn declared() {
+        let script = r#"---
+[dependencies]
+regex = "1"
+serde-json = "1"
+
+[target.'cfg(unix)'.dependencies]
+libc = "0.2"
+---
+
+use regex::Regex;
+use serde_json::Value;
+use libc;
+
+fn main() {}
+"#;
+        assert_eq!(undeclared_crates(script).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn undeclared() {
+        let script = r#"#!/usr/bin/env cargo
+use std::io;
+use ::clap::Parser;
+pub(crate) use anyhow::{Context, Result};
+#[macro_use]
+extern crate serde;
+use anyhow::bail;
+
+fn main() {
+    use itertools::Itertools;
+}
+"#;
+        assert_eq!(
+            undeclared_crates(script).unwrap(),
+            ["clap", "anyhow", "serde"]
+        );
+    }
+
+    #[test]
+    fn local_items() {
+        let script = r#"
+use crate::shapes::Circle;
+use self::Color::*;
+use Color::Red;
+use helpers::greet;
+
+mod helpers;
+mod shapes {
+    pub struct Circle;
+}
+pub enum Color {
+    Red,
+}
+
+fn main() {}
+"#;
+        assert_eq!(undeclared_crates(script).unwrap(), Vec::<String>::new());
+    }
+}
+
+#[cfg(test)]
+mod test_bin_profile_defaults {
+    use super::*;
+
+    #[test]
+    fn lib_only() {
+        let mut manifest: toml::Table = toml::from_str(
+            r#"
+[lib]
+path = "lib.rs"
+"#,
+        )
+        .unwrap();
+        add_bin_profile_defaults(&mut manifest).unwrap();
+        assert!(!manifest.contains_key("profile"), "{manifest}");
+    }
+
+    #[test]
+    fn bin() {
+        let mut manifest: toml::Table = toml::from_str(
+            r#"
+[[bin]]
+name = "script"
+path = "script.rs"
+
+[profile.release]
+strip = false
+"#,
+        )
+        .unwrap();
+        add_bin_profile_defaults(&mut manifest).unwrap();
+        assert_eq!(
+            manifest["profile"]["release"]["strip"].as_bool(),
+            Some(false)
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_edition_cargo_feature {
+    use super::*;
+
+    #[test]
+    fn stable() {
+        let mut manifest = toml::Table::new();
+        add_edition_cargo_feature(&mut manifest, Edition::Edition2021).unwrap();
+        assert!(!manifest.contains_key("cargo-features"), "{manifest}");
+    }
+
+    #[test]
+    fn unstable() {
+        // Stands in for `Edition::LATEST_STABLE` still being gated
+        let mut manifest = toml::Table::new();
+        add_edition_cargo_feature(&mut manifest, Edition::Edition2024).unwrap();
+        assert_eq!(
+            manifest["cargo-features"],
+            toml::Value::Array(vec!["edition2024".into()])
+        );
+    }
+
+    #[test]
+    fn unstable_already_enabled() {
+        let mut manifest: toml::Table =
+            toml::from_str(r#"cargo-features = ["edition2024", "open-namespaces"]"#).unwrap();
+        add_edition_cargo_feature(&mut manifest, Edition::Edition2024).unwrap();
+        assert_eq!(
+            manifest["cargo-features"],
+            toml::Value::Array(vec!["edition2024".into(), "open-namespaces".into()])
+        );
+    }
+}
+
 #[cfg(test)]
 mod test_expand {
     use snapbox::str;
@@ -372,4 +939,158 @@ strip = true
 "#]]
         );
     }
+
+    #[test]
+    fn test_doc_comment_fences_ignored() {
+        snapbox::assert_data_eq!(
+            si!(r#"//! ```rust
+//! let x = 1;
+//! ```
+//!
+//! ```cargo
+//! [dependencies]
+//! time="0.1.25"
+//! ```
+fn main() {}
+"#),
+            str![[r#"
+[[bin]]
+name = "test-"
+path = "/home/me/test.rs"
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2021"
+name = "test-"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]]
+        );
+    }
+
+    #[test]
+    fn test_prose_outside_frontmatter() {
+        snapbox::assert_data_eq!(
+            si!(r#"---cargo
+[dependencies]
+time="0.1.25"
+---
+//! A short description of what this script does.
+//!
+//! It spans more than one paragraph.
+fn main() {}
+"#),
+            str![[r#"
+[[bin]]
+name = "test-"
+path = [..]
+
+[dependencies]
+time = "0.1.25"
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2021"
+name = "test-"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]]
+        );
+    }
+
+    #[test]
+    fn test_package_name() {
+        snapbox::assert_data_eq!(
+            si!(r#"---cargo
+[package]
+name = "my-script"
+---
+fn main() {}
+"#),
+            str![[r#"
+[[bin]]
+name = "my-script"
+path = [..]
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2021"
+name = "my-script"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]]
+        );
+    }
+
+    #[test]
+    fn test_env() {
+        snapbox::assert_data_eq!(
+            si!(r#"---cargo
+[env]
+FOO = "bar"
+---
+fn main() {}
+"#),
+            str![[r#"
+[[bin]]
+name = "test-"
+path = [..]
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2021"
+name = "test-"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]]
+        );
+        assert_eq!(
+            env_config(
+                r#"---cargo
+[env]
+FOO = "bar"
+"A B" = { value = "c", force = true }
+---
+fn main() {}
+"#
+            )
+            .unwrap(),
+            [
+                r#"env."A B"={ force = true, value = "c" }"#,
+                r#"env.FOO="bar""#,
+            ]
+        );
+    }
 }
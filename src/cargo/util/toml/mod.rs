@@ -13,7 +13,6 @@ use cargo_util_schemas::manifest::{self, TomlManifest};
 use cargo_util_schemas::manifest::{RustVersion, StringOrBool};
 use itertools::Itertools;
 use lazycell::LazyCell;
-use pathdiff::diff_paths;
 use url::Url;
 
 use crate::core::compiler::{CompileKind, CompileTarget};
@@ -28,12 +27,17 @@ use crate::core::{GitReference, PackageIdSpec, SourceId, WorkspaceConfig, Worksp
 use crate::sources::{CRATES_IO_INDEX, CRATES_IO_REGISTRY};
 use crate::util::errors::{CargoResult, ManifestError};
 use crate::util::interning::InternedString;
-use crate::util::{self, context::ConfigRelativePath, GlobalContext, IntoUrl, OptVersionReq};
+use crate::util::lints::{dangling_dep_reference, is_lint_option, rel_cwd_manifest_path};
+use crate::util::{
+    self, context::ConfigRelativePath, Filesystem, GlobalContext, IntoUrl, OptVersionReq,
+};
 
 mod embedded;
+pub mod span;
 mod targets;
 
-use self::targets::to_targets;
+pub use self::embedded::{disallowed_package_keys, ExtractedPackage, DISALLOWED_KEYS};
+use self::targets::{to_targets, DuplicateTargetName};
 
 /// See also `bin/cargo/commands/run.rs`s `is_manifest_command`
 pub fn is_embedded(path: &Path) -> bool {
@@ -86,7 +90,10 @@ pub fn read_manifest(
             gctx,
             &mut warnings,
             &mut errors,
-        )?;
+        )
+        .map_err(|err| {
+            emit_undefined_workspace_field_diagnostic(err, &contents, &document, path, gctx)
+        })?;
 
         if resolved_toml.package().is_some() {
             to_real_manifest(
@@ -121,10 +128,15 @@ pub fn read_manifest(
         }
     })()
     .map_err(|err| {
+        // Already rendered against the manifest, like in `emit_diagnostic`
+        if err.is::<AlreadyPrintedError>() {
+            return err;
+        }
         ManifestError::new(
             err.context(format!("failed to parse manifest at `{}`", path.display())),
             path.into(),
         )
+        .into()
     })?;
 
     for warning in warnings {
@@ -137,8 +149,50 @@ pub fn read_manifest(
     Ok(manifest)
 }
 
+/// The directory scripts read from stdin are copied into, so they can be built like any other
+/// script
+pub fn stdin_script_dir(gctx: &GlobalContext) -> Filesystem {
+    gctx.home().join("script-stdin")
+}
+
+/// Reads the `[env]` table of the embedded manifest at `path` as `--config` values
+pub fn embedded_env_config(path: &Path) -> CargoResult<Vec<String>> {
+    let contents = paths::read(path)?;
+    embedded::env_config(&contents)
+}
+
+/// Reads the Rust source of the embedded manifest at `path`, without its shebang or frontmatter
+pub fn embedded_rust_source(path: &Path) -> CargoResult<String> {
+    let contents = paths::read(path)?;
+    Ok(embedded::rust_source(&contents)?.to_owned())
+}
+
+/// Reads the script at `path` as the files of a regular package, see [`ExtractedPackage`]
+pub fn embedded_extract_package(
+    path: &Path,
+    gctx: &GlobalContext,
+) -> CargoResult<ExtractedPackage> {
+    let contents = paths::read(path)?;
+    embedded::extract_package(&contents, path, gctx)
+}
+
+/// Reads the names of crates that the script at `path` appears to use but does not declare as
+/// dependencies in its embedded manifest
+pub fn embedded_undeclared_crates(path: &Path) -> CargoResult<Vec<String>> {
+    let contents = paths::read(path)?;
+    embedded::undeclared_crates(&contents)
+}
+
+/// Whether the embedded manifest at `path` sets `package.edition`, rather than relying on the
+/// default
+pub fn embedded_edition_is_set(path: &Path) -> CargoResult<bool> {
+    let contents = paths::read(path)?;
+    embedded::edition_is_set(&contents)
+}
+
+/// Reads the manifest at `path`, expanding it first if it is an embedded manifest
 #[tracing::instrument(skip_all)]
-fn read_toml_string(path: &Path, gctx: &GlobalContext) -> CargoResult<String> {
+pub fn read_toml_string(path: &Path, gctx: &GlobalContext) -> CargoResult<String> {
     let mut contents = paths::read(path)?;
     if is_embedded(path) {
         if !gctx.cli_unstable().script {
@@ -831,7 +885,7 @@ macro_rules! package_field_getter {
             #[doc = concat!("Gets the field `workspace.package", $key, "`.")]
             fn $field(&self) -> CargoResult<$ret> {
                 let Some(val) = self.package.as_ref().and_then(|p| p.$field.as_ref()) else  {
-                    bail!("`workspace.package.{}` was not defined", $key);
+                    return Err(self.undefined($key).into());
                 };
                 Ok(val.clone())
             }
@@ -906,7 +960,7 @@ impl InheritableFields {
     /// Gets the field `workspace.package.license-file`.
     fn license_file(&self, package_root: &Path) -> CargoResult<String> {
         let Some(license_file) = self.package.as_ref().and_then(|p| p.license_file.as_ref()) else {
-            bail!("`workspace.package.license-file` was not defined");
+            return Err(self.undefined("license-file").into());
         };
         resolve_relative_path("license-file", &self._ws_root, package_root, license_file)
     }
@@ -917,7 +971,7 @@ impl InheritableFields {
             self._ws_root.as_path(),
             self.package.as_ref().and_then(|p| p.readme.as_ref()),
         ) else {
-            bail!("`workspace.package.readme` was not defined");
+            return Err(self.undefined("readme").into());
         };
         resolve_relative_path("readme", &self._ws_root, package_root, &readme)
             .map(manifest::StringOrBool::String)
@@ -926,8 +980,31 @@ impl InheritableFields {
     fn ws_root(&self) -> &PathBuf {
         &self._ws_root
     }
+
+    fn undefined(&self, key: &'static str) -> UndefinedWorkspaceField {
+        UndefinedWorkspaceField {
+            key,
+            ws_root: self._ws_root.clone(),
+        }
+    }
 }
 
+/// A field is inherited with `<key>.workspace = true`, but is missing from
+/// `[workspace.package]`
+#[derive(Debug)]
+struct UndefinedWorkspaceField {
+    key: &'static str,
+    ws_root: PathBuf,
+}
+
+impl std::fmt::Display for UndefinedWorkspaceField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`workspace.package.{}` was not defined", self.key)
+    }
+}
+
+impl std::error::Error for UndefinedWorkspaceField {}
+
 fn field_inherit_with<'a, T>(
     field: manifest::InheritableField<T>,
     label: &str,
@@ -1187,7 +1264,8 @@ fn to_real_manifest(
             anyhow::bail!(
                 "`[project]` is not supported as of the 2024 Edition, please use `[package]`"
             );
-        } else {
+        } else if !gctx.cli_unstable().cargo_lints {
+            // Otherwise, this is reported by the `deprecated_manifest_key` lint
             warnings.push(format!("`[project]` is deprecated in favor of `[package]`"));
         }
     }
@@ -1221,7 +1299,11 @@ fn to_real_manifest(
         edition,
         &resolved_package.metabuild,
         warnings,
-    )?;
+    )
+    .map_err(|err| match err.downcast::<DuplicateTargetName>() {
+        Ok(dup) => emit_duplicate_target_diagnostic(dup, &contents, &document, manifest_file, gctx),
+        Err(err) => err,
+    })?;
 
     if targets.iter().all(|t| t.is_custom_build()) {
         bail!(
@@ -1435,21 +1517,29 @@ fn to_real_manifest(
             .unwrap_or_else(|| semver::Version::new(0, 0, 0)),
         source_id,
     );
+    let mut feature_map: BTreeMap<InternedString, Vec<InternedString>> = resolved_toml
+        .features
+        .as_ref()
+        .unwrap_or(&Default::default())
+        .iter()
+        .map(|(k, v)| {
+            (
+                InternedString::new(k),
+                v.iter().map(InternedString::from).collect(),
+            )
+        })
+        .collect();
+    if gctx.cli_unstable().cargo_lints {
+        // Reported by the `feature_body_references_removed_dep` lint instead of failing here
+        let dep_names: HashSet<_> = deps.iter().map(|dep| dep.name_in_toml()).collect();
+        for values in feature_map.values_mut() {
+            values.retain(|value| dangling_dep_reference(*value, &dep_names).is_none());
+        }
+    }
     let summary = Summary::new(
         pkgid,
         deps,
-        &resolved_toml
-            .features
-            .as_ref()
-            .unwrap_or(&Default::default())
-            .iter()
-            .map(|(k, v)| {
-                (
-                    InternedString::new(k),
-                    v.iter().map(InternedString::from).collect(),
-                )
-            })
-            .collect(),
+        &feature_map,
         resolved_package.links.as_deref(),
         rust_version.clone(),
     )?;
@@ -1551,7 +1641,12 @@ fn to_real_manifest(
                 .to_owned(),
         );
     }
-    warn_on_unused(&manifest.original_toml()._unused_keys, warnings);
+    let mut unused = manifest.original_toml()._unused_keys.clone();
+    if gctx.cli_unstable().cargo_lints {
+        // Otherwise, these are reported by the `unknown_profile_field` lint
+        unused.retain(|key| !key.starts_with("profile."));
+    }
+    warn_on_unused(&unused, warnings);
 
     manifest.feature_gate()?;
 
@@ -2283,9 +2378,12 @@ supported tools: {}",
                     // manually report unused manifest key warning since we collect all the "extra"
                     // keys and values inside the config table
                     //
-                    // except for `rust.unexpected_cfgs.check-cfg` which is used by rustc/rustdoc
-                    if !(tool == "rust" && name == "unexpected_cfgs" && config_name == "check-cfg")
-                    {
+                    // except for `rust.unexpected_cfgs.check-cfg` which is used by rustc/rustdoc,
+                    // and the options of the cargo lints that have them
+                    let is_check_cfg =
+                        tool == "rust" && name == "unexpected_cfgs" && config_name == "check-cfg";
+                    let is_option = tool == "cargo" && is_lint_option(name, config_name);
+                    if !is_check_cfg && !is_option {
                         let message =
                             format!("unused manifest key: `lints.{tool}.{name}.{config_name}`");
                         warnings.push(message);
@@ -2373,11 +2471,7 @@ fn emit_diagnostic(
         return e.into();
     };
 
-    // Get the path to the manifest, relative to the cwd
-    let manifest_path = diff_paths(manifest_file, gctx.cwd())
-        .unwrap_or_else(|| manifest_file.to_path_buf())
-        .display()
-        .to_string();
+    let manifest_path = rel_cwd_manifest_path(manifest_file, gctx);
     let message = Level::Error.title(e.message()).snippet(
         Snippet::source(contents)
             .origin(&manifest_path)
@@ -2390,6 +2484,102 @@ fn emit_diagnostic(
     return AlreadyPrintedError::new(e.into()).into();
 }
 
+/// Point at the declarations of a duplicated target name
+fn emit_duplicate_target_diagnostic(
+    dup: DuplicateTargetName,
+    contents: &str,
+    document: &toml_edit::ImDocument<String>,
+    manifest_file: &Path,
+    gctx: &GlobalContext,
+) -> anyhow::Error {
+    let spans = document
+        .as_item()
+        .get(dup.table())
+        .and_then(|item| item.as_array_of_tables())
+        .map(|targets| {
+            targets
+                .iter()
+                .filter_map(|target| target.get("name"))
+                .filter(|name| name.as_str() == Some(dup.name.as_str()))
+                .filter_map(|name| name.span())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    // Inferred targets have nothing to point at
+    let [first, .., duplicate] = spans.as_slice() else {
+        return dup.into();
+    };
+
+    let title = dup.to_string();
+    let manifest_path = rel_cwd_manifest_path(manifest_file, gctx);
+    let message = Level::Error.title(&title).snippet(
+        Snippet::source(contents)
+            .origin(&manifest_path)
+            .fold(true)
+            .annotation(Level::Error.span(duplicate.clone()))
+            .annotation(Level::Note.span(first.clone()).label("first defined here")),
+    );
+    if let Err(err) = gctx.shell().print_message(message) {
+        return err.into();
+    }
+    AlreadyPrintedError::new(dup.into()).into()
+}
+
+/// Point at the `<key>.workspace = true` marker of a field that the workspace
+/// does not define
+fn emit_undefined_workspace_field_diagnostic(
+    err: anyhow::Error,
+    contents: &str,
+    document: &toml_edit::ImDocument<String>,
+    manifest_file: &Path,
+    gctx: &GlobalContext,
+) -> anyhow::Error {
+    let Some(undefined) = err.downcast_ref::<UndefinedWorkspaceField>() else {
+        return err;
+    };
+    let Some((key, item)) = document
+        .as_item()
+        .get("package")
+        .and_then(|package| package.as_table_like())
+        .and_then(|package| package.get_key_value(undefined.key))
+    else {
+        return err;
+    };
+    let marker = item
+        .as_table_like()
+        .and_then(|field| field.get("workspace"))
+        .and_then(|workspace| workspace.span());
+    // Inline tables have a span covering the closing brace, dotted keys do not
+    let (Some(key_span), Some(end)) = (key.span(), item.span().or(marker).map(|span| span.end))
+    else {
+        return err;
+    };
+
+    let title = format!(
+        "`{key}` is inherited from `workspace.package.{key}`, which is not defined",
+        key = undefined.key
+    );
+    let ws_manifest_path = rel_cwd_manifest_path(&undefined.ws_root.join("Cargo.toml"), gctx);
+    let help = format!(
+        "add `{}` to `[workspace.package]` in `{ws_manifest_path}`",
+        undefined.key
+    );
+    let manifest_path = rel_cwd_manifest_path(manifest_file, gctx);
+    let message = Level::Error
+        .title(&title)
+        .snippet(
+            Snippet::source(contents)
+                .origin(&manifest_path)
+                .fold(true)
+                .annotation(Level::Error.span(key_span.start..end)),
+        )
+        .footer(Level::Help.title(&help));
+    if let Err(err) = gctx.shell().print_message(message) {
+        return err.into();
+    }
+    AlreadyPrintedError::new(err).into()
+}
+
 /// Warn about paths that have been deprecated and may conflict.
 fn deprecated_underscore<T>(
     old: &Option<T>,
@@ -0,0 +1,199 @@
+//! Locating keys and values within a parsed manifest.
+//!
+//! This is what `cargo`'s own lints use to point at the part of a manifest
+//! they report on, and is exposed so other tooling doesn't have to
+//! reimplement the traversal.
+//!
+//! A location is given as a path of segments, where each segment is one of:
+//!
+//!  * a table key, like `["package", "edition"]` or
+//!    `["target", "cfg(unix)", "dependencies", "bar"]`
+//!  * a string value of an array, like `["features", "default", "std"]`;
+//!    this has to be the last segment
+//!  * a `<key>=<value>` selector for the first table of an array of tables
+//!    whose `<key>` is the string `<value>`, like `["bin", "name=foo", "path"]`
+
+use std::ops::Range;
+
+use toml_edit::{ImDocument, Item, Key, TableLike};
+
+/// The location of a key and its value in a manifest, as byte offsets into
+/// the manifest's contents
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TomlSpan {
+    /// The span of the key
+    ///
+    /// For a dotted key, this covers the whole key, like `bar.workspace`.
+    /// For array elements, which have no key, this is the same as `value`.
+    pub key: Range<usize>,
+    /// The span of the value
+    pub value: Range<usize>,
+}
+
+/// Finds the key and value at `path` in `document`
+///
+/// See the [module documentation](self) for the syntax of `path`.
+pub fn get_key_value_span(document: &ImDocument<String>, path: &[&str]) -> Option<TomlSpan> {
+    let mut table = document.as_item().as_table_like()?;
+    let mut segments = path.iter();
+    while let Some(segment) = segments.next() {
+        let (key, item) = table.get_key_value(segment)?;
+        if let Some(sub) = item.as_table_like() {
+            if segments.len() == 0 {
+                return key_value_span(key, item);
+            }
+            table = sub;
+            continue;
+        }
+        let Some(next) = segments.next() else {
+            return key_value_span(key, item);
+        };
+        let is_last = segments.len() == 0;
+        let Some((elem, span)) = select_table(item, next) else {
+            return string_value_span(item, next, is_last);
+        };
+        if is_last {
+            let value = span?;
+            return Some(TomlSpan {
+                key: value.clone(),
+                value,
+            });
+        }
+        table = elem;
+    }
+    None
+}
+
+fn key_value_span(key: &Key, item: &Item) -> Option<TomlSpan> {
+    let decor = key.dotted_decor();
+    let key = match (
+        decor.prefix().and_then(|p| p.span()),
+        decor.suffix().and_then(|s| s.span()),
+    ) {
+        (Some(prefix), Some(suffix)) => prefix.start..suffix.end,
+        _ => key.span()?,
+    };
+    // Dotted keys have no span of their own, so they are their own value
+    let value = item.span().unwrap_or_else(|| key.clone());
+    Some(TomlSpan { key, value })
+}
+
+/// Finds the table in the array (of tables) `item` picked by the
+/// `<key>=<value>` `selector`
+fn select_table<'a>(
+    item: &'a Item,
+    selector: &str,
+) -> Option<(&'a dyn TableLike, Option<Range<usize>>)> {
+    let (field, name) = selector.split_once('=')?;
+    let matches = |t: &dyn TableLike| t.get(field).and_then(|v| v.as_str()) == Some(name);
+    if let Some(tables) = item.as_array_of_tables() {
+        let elem = tables.iter().find(|t| matches(*t))?;
+        return Some((elem, elem.span()));
+    }
+    let elem = item
+        .as_array()?
+        .iter()
+        .filter_map(|v| v.as_inline_table())
+        .find(|t| matches(*t))?;
+    Some((elem, elem.span()))
+}
+
+/// Finds the string `value` in the array `item`, which must be the last
+/// segment of the path
+fn string_value_span(item: &Item, value: &str, is_last: bool) -> Option<TomlSpan> {
+    if !is_last {
+        return None;
+    }
+    let value = item
+        .as_array()?
+        .iter()
+        .find(|v| v.as_str() == Some(value))?
+        .span()?;
+    Some(TomlSpan {
+        key: value.clone(),
+        value,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_key_value_span, TomlSpan};
+    use toml_edit::ImDocument;
+
+    const MANIFEST: &str = r#"[package]
+name = "foo"
+edition.workspace = true
+
+[features]
+default = ["std", "dep:bar"]
+
+[target.'cfg(unix)'.dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[[bin]]
+name = "a"
+path = "src/a.rs"
+
+[[bin]]
+name = "b"
+path = "src/b.rs"
+
+[[example]]
+name = "c"
+"#;
+
+    fn span_of<'a>(path: &[&str]) -> Option<(&'a str, &'a str)> {
+        let document = ImDocument::parse(MANIFEST.to_owned()).unwrap();
+        get_key_value_span(&document, path)
+            .map(|TomlSpan { key, value }| (&MANIFEST[key], &MANIFEST[value]))
+    }
+
+    #[test]
+    fn nested_tables() {
+        assert_eq!(span_of(&["package", "name"]), Some(("name", "\"foo\"")));
+        assert_eq!(
+            span_of(&["target", "cfg(unix)", "dependencies", "bar", "optional"]),
+            Some(("optional", "true"))
+        );
+        assert_eq!(
+            span_of(&["target", "cfg(unix)", "dependencies", "bar"]),
+            Some(("bar", "{ version = \"0.1.0\", optional = true }"))
+        );
+        assert_eq!(span_of(&["package", "license"]), None);
+        assert_eq!(span_of(&["package", "name", "extra"]), None);
+    }
+
+    #[test]
+    fn dotted_keys() {
+        assert_eq!(
+            span_of(&["package", "edition"]),
+            Some(("edition.workspace", "edition.workspace"))
+        );
+        assert_eq!(
+            span_of(&["package", "edition", "workspace"]),
+            Some(("workspace", "true"))
+        );
+    }
+
+    #[test]
+    fn string_arrays() {
+        assert_eq!(
+            span_of(&["features", "default", "dep:bar"]),
+            Some(("\"dep:bar\"", "\"dep:bar\""))
+        );
+        assert_eq!(span_of(&["features", "default", "alloc"]), None);
+        assert_eq!(span_of(&["features", "default", "std", "extra"]), None);
+    }
+
+    #[test]
+    fn arrays_of_tables() {
+        assert_eq!(
+            span_of(&["bin", "name=b", "path"]),
+            Some(("path", "\"src/b.rs\""))
+        );
+        assert_eq!(span_of(&["bin", "name=a", "name"]), Some(("name", "\"a\"")));
+        assert_eq!(span_of(&["example", "name=c"]).map(|_| ()), Some(()));
+        assert_eq!(span_of(&["bin", "name=c", "path"]), None);
+        assert_eq!(span_of(&["bin", "a", "path"]), None);
+    }
+}
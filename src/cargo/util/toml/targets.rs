@@ -818,21 +818,51 @@ fn inferred_to_toml_targets(inferred: &[(String, PathBuf)]) -> Vec<TomlTarget> {
 }
 
 /// Will check a list of toml targets, and make sure the target names are unique within a vector.
-fn validate_unique_names(targets: &[TomlTarget], target_kind: &str) -> CargoResult<()> {
+fn validate_unique_names(targets: &[TomlTarget], target_kind: &'static str) -> CargoResult<()> {
     let mut seen = HashSet::new();
     for name in targets.iter().map(|e| name_or_panic(e)) {
         if !seen.insert(name) {
-            anyhow::bail!(
-                "found duplicate {target_kind} name {name}, \
-                 but all {target_kind} targets must have a unique name",
-                target_kind = target_kind,
-                name = name
-            );
+            return Err(DuplicateTargetName {
+                target_kind,
+                name: name.to_owned(),
+            }
+            .into());
         }
     }
     Ok(())
 }
 
+/// Two targets of the same kind share a name
+#[derive(Debug)]
+pub(super) struct DuplicateTargetName {
+    pub target_kind: &'static str,
+    pub name: String,
+}
+
+impl DuplicateTargetName {
+    /// The manifest key the targets are declared under
+    pub fn table(&self) -> &'static str {
+        match self.target_kind {
+            "binary" => "bin",
+            kind => kind,
+        }
+    }
+}
+
+impl std::fmt::Display for DuplicateTargetName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "found duplicate {target_kind} name {name}, \
+             but all {target_kind} targets must have a unique name",
+            target_kind = self.target_kind,
+            name = self.name
+        )
+    }
+}
+
+impl std::error::Error for DuplicateTargetName {}
+
 fn configure(toml: &TomlTarget, target: &mut Target) -> CargoResult<()> {
     let t2 = target.clone();
     target
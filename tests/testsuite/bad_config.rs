@@ -498,10 +498,50 @@ fn duplicate_binary_names() {
     p.cargo("check")
         .with_status(101)
         .with_stderr_data(str![[r#"
-[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+[ERROR] found duplicate binary name e, but all binary targets must have a unique name
+  --> Cargo.toml:13:23
+   |
+ 9 |                name = "e"
+   |                       --- first defined here
+10 |                path = "a.rs"
+11 |
+12 |                [[bin]]
+13 |                name = "e"
+   |                       ^^^
+   |
 
-Caused by:
-  found duplicate binary name e, but all binary targets must have a unique name
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explicit_bin_named_like_inferred_bin() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+               [package]
+               name = "qqq"
+               version = "0.1.0"
+               edition = "2021"
+               authors = ["A <a@a.a>"]
+
+               [[bin]]
+               name = "e"
+               path = "src/a.rs"
+            "#,
+        )
+        .file("src/a.rs", r#"fn main() -> () {}"#)
+        .file(
+            "src/bin/e.rs",
+            r#"compile_error!("the inferred `e` should be replaced by the explicit one");"#,
+        )
+        .build();
+
+    p.cargo("check")
+        .with_stderr_data(str![[r#"
+[CHECKING] qqq v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 
 "#]])
         .run();
@@ -535,10 +575,17 @@ fn duplicate_example_names() {
     p.cargo("check --example ex")
         .with_status(101)
         .with_stderr_data(str![[r#"
-[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
-
-Caused by:
-  found duplicate example name ex, but all example targets must have a unique name
+[ERROR] found duplicate example name ex, but all example targets must have a unique name
+  --> Cargo.toml:13:23
+   |
+ 9 |                name = "ex"
+   |                       ---- first defined here
+10 |                path = "examples/ex.rs"
+11 |
+12 |                [[example]]
+13 |                name = "ex"
+   |                       ^^^^
+   |
 
 "#]])
         .run();
@@ -572,10 +619,17 @@ fn duplicate_bench_names() {
     p.cargo("bench")
         .with_status(101)
         .with_stderr_data(str![[r#"
-[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
-
-Caused by:
-  found duplicate bench name ex, but all bench targets must have a unique name
+[ERROR] found duplicate bench name ex, but all bench targets must have a unique name
+  --> Cargo.toml:13:23
+   |
+ 9 |                name = "ex"
+   |                       ---- first defined here
+10 |                path = "benches/ex.rs"
+11 |
+12 |                [[bench]]
+13 |                name = "ex"
+   |                       ^^^^
+   |
 
 "#]])
         .run();
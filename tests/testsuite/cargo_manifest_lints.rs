@@ -0,0 +1,272 @@
+//! Tests for the `cargo manifest-lints` command.
+
+use cargo_test_support::basic_manifest;
+use cargo_test_support::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn gated() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("manifest-lints check")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `cargo manifest-lints` command is unstable, pass `-Z cargo-lints` to enable it
+See https://github.com/rust-lang/cargo/issues/12235 for more information about the `cargo manifest-lints` command.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn reports_lints_without_compiling() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+foo = ["foo"]
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `foo` activates itself
+ --> Cargo.toml:8:8
+  |
+8 | foo = ["foo"]
+  |        -----
+  |
+  = [NOTE] `cargo::self_referential_feature` is set to `warn` by default
+  = [HELP] remove `"foo"`
+
+"#]])
+        .run();
+    assert!(!p.root().join("target").exists());
+}
+
+#[cargo_test]
+fn fails_on_error_level_lints() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar"]
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+bar = ["bar"]
+
+[lints.cargo]
+self_referential_feature = "deny"
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] feature `bar` activates itself
+ --> bar/Cargo.toml:8:8
+  |
+8 | bar = ["bar"]
+  |        ^^^^^
+  |
+  = [NOTE] `cargo::self_referential_feature` is set to `deny` in `[lints]`
+  = [HELP] remove `"bar"`
+[ERROR] could not check lints due to 1 previous error
+
+"#]])
+        .run();
+    assert!(!p.root().join("target").exists());
+}
+
+#[cargo_test]
+fn counts_errors_across_members() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar", "baz"]
+resolver = "2"
+"#,
+        )
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+bar = ["bar"]
+
+[lints.cargo]
+self_referential_feature = "deny"
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .file(
+            "baz/Cargo.toml",
+            r#"
+[package]
+name = "baz"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+baz = ["baz"]
+
+[lints.cargo]
+self_referential_feature = "deny"
+"#,
+        )
+        .file("baz/src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(
+            str![[r#"
+[ERROR] feature `bar` activates itself
+ --> bar/Cargo.toml:8:8
+  |
+8 | bar = ["bar"]
+  |        ^^^^^
+  |
+  = [NOTE] `cargo::self_referential_feature` is set to `deny` in `[lints]`
+  = [HELP] remove `"bar"`
+[ERROR] feature `baz` activates itself
+ --> baz/Cargo.toml:8:8
+  |
+8 | baz = ["baz"]
+  |        ^^^^^
+  |
+  = [NOTE] `cargo::self_referential_feature` is set to `deny` in `[lints]`
+  = [HELP] remove `"baz"`
+[ERROR] could not check lints due to 2 previous errors
+
+"#]]
+            .unordered(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn only_allowlisted_lints() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { path = "bar", default-features = true }
+
+[features]
+foo = ["foo"]
+
+[lints.cargo]
+redundant_default_features_true = "deny"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints --lint self_referential_feature")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `foo` activates itself
+  --> Cargo.toml:11:8
+   |
+11 | foo = ["foo"]
+   |        -----
+   |
+   = [NOTE] `cargo::self_referential_feature` is set to `warn` by default
+   = [HELP] remove `"foo"`
+
+"#]])
+        .run();
+
+    p.cargo("manifest-lints check -Zcargo-lints --lint redundant_default_features_true")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `default-features = true` is redundant
+ --> Cargo.toml:8:23
+  |
+8 | bar = { path = "bar", default-features = true }
+  |                       ^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::redundant_default_features_true` is set to `deny` in `[lints]`
+  = [HELP] remove `default-features`
+[ERROR] could not check lints due to 1 previous error
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn unknown_allowlisted_lint() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("manifest-lints check -Zcargo-lints --lint self_referential_featur")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] unknown lint `self_referential_featur`
+
+	Did you mean `self_referential_feature`?
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn list_shows_since() {
+    let p = project().file("src/lib.rs", "").build();
+
+    p.cargo("manifest-lints list -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stdout_data(str![[r#"
+...
+unknown_lints[..]warn   1.78   unknown lint
+...
+"#]])
+        .run();
+}
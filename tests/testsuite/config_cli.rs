@@ -179,6 +179,27 @@ fn merges_array() {
     );
 }
 
+#[cargo_test]
+fn default_cli_config() {
+    // Defaults come before the command-line and are only merged once.
+    write_config_toml(
+        "
+        [build]
+        rustflags = ['--file']
+        ",
+    );
+    let mut gctx = GlobalContextBuilder::new()
+        .config_arg("build.rustflags = ['--cli']")
+        .build();
+    gctx.add_default_cli_config(vec!["build.rustflags = ['--default']".to_owned()]);
+    let cwd = gctx.cwd().to_owned();
+    gctx.reload_rooted_at(cwd).unwrap();
+    assert_eq!(
+        gctx.get::<Vec<String>>("build.rustflags").unwrap(),
+        ["--file", "--default", "--cli"]
+    );
+}
+
 #[cargo_test]
 fn string_list_array() {
     // Using the StringList type.
@@ -2264,3 +2264,58 @@ fn invalid_feature_name_slash_error() {
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn invalid_feature_name_leading_dash_error() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.1.0"
+                edition = "2015"
+
+                [features]
+                -foo = []
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] invalid character `-` in feature name: `-foo`, the first character must be a Unicode XID start character or digit (most letters or `_` or `0` to `9`)
+ --> Cargo.toml:8:17
+  |
+8 |                 -foo = []
+  |                 ^^^^
+  |
+
+"#]])
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+            [package]
+            name = "foo"
+            version = "0.1.0"
+            edition = "2015"
+
+            [features]
+            foo-bar_baz = []
+            "c++17" = []
+            "foo.1" = []
+        "#,
+    );
+
+    p.cargo("check")
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
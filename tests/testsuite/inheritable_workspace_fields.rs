@@ -2,6 +2,7 @@
 #![allow(deprecated)]
 
 use cargo_test_support::registry::{Dependency, Package, RegistryBuilder};
+use cargo_test_support::str;
 use cargo_test_support::{
     basic_lib_manifest, basic_manifest, git, path2url, paths, project, publish, registry,
 };
@@ -585,15 +586,78 @@ fn inherit_from_own_undefined_field() {
         .with_status(101)
         .with_stderr(
             "\
-[ERROR] failed to parse manifest at `[CWD]/Cargo.toml`
+[ERROR] `description` is inherited from `workspace.package.description`, which is not defined
+ --> Cargo.toml:7:13
+  |
+7 |             description.workspace = true
+  |             ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = help: add `description` to `[workspace.package]` in `Cargo.toml`
+",
+        )
+        .run();
+}
 
-Caused by:
-  error inheriting `description` from workspace root manifest's `workspace.package.description`
+#[cargo_test]
+fn inherit_undefined_field_from_workspace() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["bar"]
 
-Caused by:
-  `workspace.package.description` was not defined
-",
+[workspace.package]
+license = "MIT"
+"#,
+        )
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = { workspace = true }
+edition = "2015"
+license.workspace = true
+"#,
         )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `version` is inherited from `workspace.package.version`, which is not defined
+ --> bar/Cargo.toml:4:1
+  |
+4 | version = { workspace = true }
+  | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = [HELP] add `version` to `[workspace.package]` in `Cargo.toml`
+[ERROR] failed to load manifest for workspace member `[ROOT]/foo/bar`
+referenced by workspace at `[ROOT]/foo/Cargo.toml`
+
+"#]])
+        .run();
+
+    p.change_file(
+        "Cargo.toml",
+        r#"
+[workspace]
+members = ["bar"]
+
+[workspace.package]
+version = "0.1.0"
+license = "MIT"
+"#,
+    );
+    p.cargo("check")
+        .with_stderr_data(str![[r#"
+[LOCKING] 1 package to latest compatible version
+[CHECKING] bar v0.1.0 ([ROOT]/foo/bar)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
         .run();
 }
 
@@ -0,0 +1,71 @@
+use cargo_test_support::basic_manifest;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn broad_glob() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["**/member-*"]
+
+[workspace.lints.cargo]
+broad_members_glob = "warn"
+"#,
+        )
+        .file(
+            "crates/member-a/Cargo.toml",
+            &basic_manifest("member-a", "0.1.0"),
+        )
+        .file("crates/member-a/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `**/member-*` searches every directory of the workspace for members
+ --> Cargo.toml:3:12
+  |
+3 | members = ["**/member-*"]
+  |            -------------
+  |
+  = [NOTE] `cargo::broad_members_glob` is set to `warn` in `[lints]`
+  = [HELP] narrow the glob to the directories that contain members, like `crates/*`
+[CHECKING] member-a v0.1.0 ([ROOT]/foo/crates/member-a)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn narrow_glob() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["crates/*", "crates/**/member-*"]
+
+[workspace.lints.cargo]
+broad_members_glob = "warn"
+"#,
+        )
+        .file(
+            "crates/member-a/Cargo.toml",
+            &basic_manifest("member-a", "0.1.0"),
+        )
+        .file("crates/member-a/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] member-a v0.1.0 ([ROOT]/foo/crates/member-a)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
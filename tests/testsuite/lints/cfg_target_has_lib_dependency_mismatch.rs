@@ -0,0 +1,83 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn optional_in_one_target() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[target.'cfg(unix)'.dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[target.'cfg(windows)'.dependencies]
+bar = { version = "0.1.0", default-features = false }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `bar` sets `optional` and `default-features` differently than in `[target.'cfg(unix)'.dependencies]`
+  --> Cargo.toml:11:1
+   |
+ 8 | bar = { version = "0.1.0", optional = true }
+   | --- first declared here
+ 9 |
+10 | [target.'cfg(windows)'.dependencies]
+11 | bar = { version = "0.1.0", default-features = false }
+   | ---
+   |
+   = [NOTE] `cargo::cfg_target_has_lib_dependency_mismatch` is set to `warn` by default
+   = [HELP] use the same `optional` and `default-features` for every target
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn same_settings() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[target.'cfg(unix)'.dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[target.'cfg(windows)'.dependencies]
+bar = { version = "0.1.0", optional = true, default-features = true }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,148 @@
+use cargo_test_support::basic_manifest;
+use cargo_test_support::project;
+use cargo_test_support::registry::alt_init;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn path_and_registry() {
+    alt_init();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { path = "bar", registry = "alternative" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `registry` is ignored for dependency `bar`
+ --> Cargo.toml:8:23
+  |
+8 | bar = { path = "bar", registry = "alternative" }
+  |         ----          ^^^^^^^^
+  |         |
+  |         `cargo` uses this source
+  |
+  = [NOTE] `cargo::conflicting_dependency_source` is set to `deny` by default
+  = [HELP] remove `registry`, or add a `version` to use when publishing
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn path_and_registry_with_version() {
+    alt_init();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { path = "bar", version = "0.1.0", registry = "alternative" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] bar v0.1.0 ([ROOT]/foo/bar)
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+// `git` with `path` or `registry` is rejected when the manifest is loaded, so
+// these never reach the lint
+#[cargo_test]
+fn git_and_path() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { git = "https://example.com/bar", path = "bar" }
+
+[lints.cargo]
+conflicting_dependency_source = "allow"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  dependency (bar) specification is ambiguous. Only one of `git` or `path` is allowed.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn registry_and_git() {
+    alt_init();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { git = "https://example.com/bar", registry = "alternative" }
+
+[lints.cargo]
+conflicting_dependency_source = "allow"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  dependency (bar) specification is ambiguous. Only one of `git` or `registry` is allowed.
+
+"#]])
+        .run();
+}
@@ -0,0 +1,117 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+use cargo_test_support::{basic_manifest, Project};
+
+/// Creates a package at `a/b/c/foo` with path dependencies going up one,
+/// three, and four parent directories
+fn nested_project(lint: &str) -> Project {
+    project()
+        .no_manifest()
+        .file(
+            "a/b/c/foo/Cargo.toml",
+            &format!(
+                r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = {{ path = "../bar" }}
+baz = {{ path = "../../../baz" }}
+qux = {{ path = "../../../../qux" }}
+
+[lints.cargo]
+deep_path_dependency = {lint}
+"#
+            ),
+        )
+        .file("a/b/c/foo/src/lib.rs", "")
+        .file("a/b/c/bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("a/b/c/bar/src/lib.rs", "")
+        .file("a/baz/Cargo.toml", &basic_manifest("baz", "0.1.0"))
+        .file("a/baz/src/lib.rs", "")
+        .file("qux/Cargo.toml", &basic_manifest("qux", "0.1.0"))
+        .file("qux/src/lib.rs", "")
+        .build()
+}
+
+#[cargo_test]
+fn default_max_depth() {
+    let p = nested_project(r#""warn""#);
+
+    p.cargo("check -Zcargo-lints")
+        .cwd("a/b/c/foo")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] path dependency `qux` is outside of the package
+  --> Cargo.toml:10:16
+   |
+10 | qux = { path = "../../../../qux" }
+   |                -----------------
+   |
+   = [NOTE] `cargo::deep_path_dependency` is set to `warn` in `[lints]`
+   = [NOTE] the path goes up 4 parent directories, more than the 3 allowed
+[LOCKING] 4 packages to latest compatible versions
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn configured_max_depth() {
+    let p = nested_project(r#"{ level = "warn", max-depth = 4 }"#);
+
+    p.cargo("check -Zcargo-lints")
+        .cwd("a/b/c/foo")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 4 packages to latest compatible versions
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn lower_max_depth() {
+    let p = nested_project(r#"{ level = "warn", max-depth = 2 }"#);
+
+    p.cargo("check -Zcargo-lints")
+        .cwd("a/b/c/foo")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] path dependency `baz` is outside of the package
+ --> Cargo.toml:9:16
+  |
+9 | baz = { path = "../../../baz" }
+  |                --------------
+  |
+  = [NOTE] `cargo::deep_path_dependency` is set to `warn` in `[lints]`
+  = [NOTE] the path goes up 3 parent directories, more than the 2 allowed
+[WARNING] path dependency `qux` is outside of the package
+  --> Cargo.toml:10:16
+   |
+10 | qux = { path = "../../../../qux" }
+   |                -----------------
+   |
+   = [NOTE] the path goes up 4 parent directories, more than the 2 allowed
+[LOCKING] 4 packages to latest compatible versions
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn invalid_max_depth() {
+    let p = nested_project(r#"{ level = "warn", max-depth = "4" }"#);
+
+    p.cargo("check -Zcargo-lints")
+        .cwd("a/b/c/foo")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `max-depth` for `cargo::deep_path_dependency` must be a non-negative integer, but found "4"
+
+"#]])
+        .run();
+}
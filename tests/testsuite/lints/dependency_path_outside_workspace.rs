@@ -0,0 +1,87 @@
+use cargo_test_support::basic_manifest;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn outside_workspace() {
+    let p = project()
+        .no_manifest()
+        .file(
+            "ws/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[workspace]
+
+[dependencies]
+bar = { path = "bar" }
+baz = { path = "../baz" }
+
+[lints.cargo]
+dependency_path_outside_workspace = "warn"
+"#,
+        )
+        .file("ws/src/lib.rs", "")
+        .file("ws/bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("ws/bar/src/lib.rs", "")
+        .file("baz/Cargo.toml", &basic_manifest("baz", "0.1.0"))
+        .file("baz/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .cwd("ws")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] path dependency `baz` is outside of the workspace
+  --> Cargo.toml:11:16
+   |
+11 | baz = { path = "../baz" }
+   |                --------
+   |
+   = [NOTE] `cargo::dependency_path_outside_workspace` is set to `warn` in `[lints]`
+   = [NOTE] it is not under the workspace's version control, and `Cargo.lock` doesn't pin its contents
+[LOCKING] 3 packages to latest compatible versions
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn inside_workspace() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[workspace]
+
+[dependencies]
+bar = { path = "bar" }
+baz = { path = "crates/baz" }
+
+[lints.cargo]
+dependency_path_outside_workspace = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.1.0"))
+        .file("bar/src/lib.rs", "")
+        .file("crates/baz/Cargo.toml", &basic_manifest("baz", "0.1.0"))
+        .file("crates/baz/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 3 packages to latest compatible versions
+...
+"#]])
+        .run();
+}
@@ -0,0 +1,73 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn not_configured() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[workspace.dependencies]
+bar = { version = "0.1.0", registry = "my-registry" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] registry `my-registry` of dependency `bar` is not configured
+ --> Cargo.toml:8:39
+  |
+8 | bar = { version = "0.1.0", registry = "my-registry" }
+  |                                       ^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::dependency_registry_not_configured` is set to `deny` by default
+  = [HELP] set the registry's index in `[registries.my-registry]` of a cargo config file, like `.cargo/config.toml`
+[ERROR] encountered 1 errors(s) while running lints
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn configured() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[workspace.dependencies]
+bar = { version = "0.1.0", registry = "my-registry" }
+"#,
+        )
+        .file(
+            ".cargo/config.toml",
+            r#"
+[registries.my-registry]
+index = "https://example.com/index"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,111 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+const MANIFEST: &str = r#"
+[project]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+"#;
+
+#[cargo_test]
+fn warn() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `project` is deprecated in favor of `package`
+ --> Cargo.toml:2:2
+  |
+2 | [project]
+  |  -------
+  |
+  = [NOTE] `cargo::deprecated_manifest_key` is set to `warn` by default
+  = [HELP] rename `project` to `package`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allow() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[project]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[lints.cargo]
+deprecated_manifest_key = "allow"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (1 fix)
+...
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+"#]],
+    );
+}
+
+#[cargo_test]
+fn json_message_format() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints --message-format json")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stdout_data(
+            str![[r#"
+{"applicability":"MachineApplicable","level":"warn","manifest_path":"[ROOT]/foo/Cargo.toml","message":"`project` is deprecated in favor of `package`","name":"deprecated_manifest_key","reason":"cargo-manifest-lint","since":"1.82","span":{"end":9,"start":2}}
+{"executable":null,"features":[],"filenames":"{...}","fresh":false,"manifest_path":"[ROOT]/foo/Cargo.toml","package_id":"path+[ROOTURL]/foo#0.1.0","profile":"{...}","reason":"compiler-artifact","target":"{...}"}
+{"reason":"build-finished","success":true}
+
+"#]]
+            .json_lines(),
+        )
+        .run();
+}
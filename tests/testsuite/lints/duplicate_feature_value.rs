@@ -0,0 +1,118 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+const MANIFEST: &str = r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+foo = ["bar", "baz", "bar"]
+bar = []
+baz = []
+"#;
+
+#[cargo_test]
+fn warn() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `foo` lists `bar` more than once
+ --> Cargo.toml:8:22
+  |
+8 | foo = ["bar", "baz", "bar"]
+  |                      -----
+  |
+  = [NOTE] `cargo::duplicate_feature_value` is set to `warn` by default
+  = [HELP] remove the duplicate `"bar"`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (1 fix)
+...
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+foo = ["bar", "baz"]
+bar = []
+baz = []
+
+"#]],
+    );
+}
+
+#[cargo_test]
+fn near_duplicates() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { path = "bar", optional = true }
+
+[features]
+foo = ["dep:bar", "bar/std", "bar?/std", "std"]
+std = []
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file(
+            "bar/Cargo.toml",
+            r#"
+[package]
+name = "bar"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+std = []
+"#,
+        )
+        .file("bar/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,135 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn implicit_activation() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+baz = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["bar"]
+all = ["foo", "baz"]
+
+[lints.cargo]
+feature_activates_default_of_optional_dep = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `all` implicitly activates the optional dependency `baz`
+  --> Cargo.toml:13:15
+   |
+13 | all = ["foo", "baz"]
+   |               -----
+   |
+   = [NOTE] `cargo::feature_activates_default_of_optional_dep` is set to `warn` in `[lints]`
+   = [NOTE] `baz` is the implicit feature created for the optional dependency `baz`
+   = [HELP] activate the dependency explicitly with `"dep:baz"`
+[WARNING] feature `foo` implicitly activates the optional dependency `bar`
+  --> Cargo.toml:12:8
+   |
+12 | foo = ["bar"]
+   |        -----
+   |
+   = [NOTE] `bar` is the implicit feature created for the optional dependency `bar`
+   = [HELP] activate the dependency explicitly with `"dep:bar"`
+[UPDATING] `dummy-registry` index
+[LOCKING] 3 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explicit_activation() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+baz = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["dep:bar"]
+baz = ["dep:baz"]
+all = ["foo", "baz"]
+
+[lints.cargo]
+feature_activates_default_of_optional_dep = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 3 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allowed_by_default() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["bar"]
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,106 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn dangling_dep() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+serde = ["dep:serde"]
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] feature `serde` includes `dep:serde`, but `serde` is not a dependency
+ --> Cargo.toml:8:10
+  |
+8 | serde = ["dep:serde"]
+  |          ^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::feature_body_references_removed_dep` is set to `deny` by default
+  = [HELP] remove `"dep:serde"`, or declare `serde` as a dependency
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn dangling_weak_dep_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+std = ["bar?/std"]
+
+[lints.cargo]
+feature_body_references_removed_dep = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `std` includes `bar?/std`, but `bar` is not a dependency
+ --> Cargo.toml:8:8
+  |
+8 | std = ["bar?/std"]
+  |        ----------
+  |
+  = [NOTE] `cargo::feature_body_references_removed_dep` is set to `warn` in `[lints]`
+  = [HELP] remove `"bar?/std"`, or declare `bar` as a dependency
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn dangling_without_cargo_lints() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+std = ["bar?/std"]
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  feature `std` includes `bar?/std`, but `bar` is not a dependency
+
+"#]])
+        .run();
+}
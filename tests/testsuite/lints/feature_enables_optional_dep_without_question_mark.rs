@@ -0,0 +1,86 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn weak_and_non_weak() {
+    Package::new("bar", "0.1.0").feature("std", &[]).publish();
+    Package::new("baz", "0.1.0").feature("std", &[]).publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+baz = { version = "0.1.0", optional = true }
+
+[features]
+std = ["bar/std", "baz?/std"]
+full = ["dep:bar", "bar/std"]
+
+[lints.cargo]
+feature_enables_optional_dep_without_question_mark = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `std` activates the optional dependency `bar` through `bar/std`
+  --> Cargo.toml:12:8
+   |
+12 | std = ["bar/std", "baz?/std"]
+   |        ---------
+   |
+   = [NOTE] `cargo::feature_enables_optional_dep_without_question_mark` is set to `warn` in `[lints]`
+   = [NOTE] `bar/std` enables `std` and also activates `bar`
+   = [HELP] if `bar` should not be activated, use `"bar?/std"`
+[UPDATING] `dummy-registry` index
+[LOCKING] 3 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allowed_by_default() {
+    Package::new("bar", "0.1.0").feature("std", &[]).publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+std = ["bar/std"]
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
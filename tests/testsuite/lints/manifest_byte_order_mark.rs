@@ -0,0 +1,60 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+const MANIFEST: &str = "\u{feff}
+[package]
+name = \"foo\"
+version = \"0.1.0\"
+edition = \"2021\"
+";
+
+#[cargo_test]
+fn warn() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] manifest starts with a byte order mark
+ --> Cargo.toml:1:1
+...
+  = [NOTE] `cargo::manifest_byte_order_mark` is set to `warn` by default
+  = [HELP] remove the byte order mark
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (1 fix)
+...
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+"#]],
+    );
+    assert!(!p.read_file("Cargo.toml").starts_with('\u{feff}'));
+}
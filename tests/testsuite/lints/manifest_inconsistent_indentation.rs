@@ -0,0 +1,84 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn inconsistent() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2015"
+authors = [
+    "a",
+   "b",
+  "c",
+]
+description = """
+  foo"""
+
+[lints.cargo]
+  manifest_inconsistent_indentation = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] line is indented by 3 spaces, which is not a multiple of 4
+ --> Cargo.toml:8:1
+  |
+8 |    "b",
+  | ---
+  |
+  = [NOTE] `cargo::manifest_inconsistent_indentation` is set to `warn` in `[lints]`
+  = [HELP] indent by a multiple of 4 spaces
+[WARNING] line is indented by 2 spaces, which is not a multiple of 4
+  --> Cargo.toml:15:1
+   |
+15 |   manifest_inconsistent_indentation = "warn"
+   | --
+   |
+   = [HELP] indent by a multiple of 4 spaces
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn consistent() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2015"
+authors = [
+  "a",
+  "b",
+]
+
+[lints.cargo]
+manifest_inconsistent_indentation = { level = "warn", indent-width = 2 }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
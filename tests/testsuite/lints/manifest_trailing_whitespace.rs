@@ -0,0 +1,109 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+const MANIFEST: &str = "
+[package]
+name = \"foo\"\x20\x20
+version = \"0.1.0\"\x20
+edition = \"2015\"
+description = \"\"\"
+foo\x20\x20
+bar\"\"\"\x20
+
+[lints.cargo]
+manifest_trailing_whitespace = \"warn\"
+";
+
+#[cargo_test]
+fn warn() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] trailing whitespace in manifest
+ --> Cargo.toml:3:13
+  |
+3 | name = "foo"[..]
+  |             --
+  |
+  = [NOTE] `cargo::manifest_trailing_whitespace` is set to `warn` in `[lints]`
+  = [HELP] remove the trailing whitespace
+[WARNING] trailing whitespace in manifest
+ --> Cargo.toml:4:18
+  |
+4 | version = "0.1.0"[..]
+  |                  -
+  |
+  = [HELP] remove the trailing whitespace
+[WARNING] trailing whitespace in manifest
+ --> Cargo.toml:8:7
+  |
+8 | bar"""[..]
+  |       -
+  |
+  = [HELP] remove the trailing whitespace
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allowed_by_default() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &MANIFEST.replace("manifest_trailing_whitespace = \"warn\"", ""),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (3 fixes)
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    // Whitespace within the multi-line string is part of its value
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        "
+[package]
+name = \"foo\"
+version = \"0.1.0\"
+edition = \"2015\"
+description = \"\"\"
+foo\x20\x20
+bar\"\"\"
+
+[lints.cargo]
+manifest_trailing_whitespace = \"warn\"
+",
+    );
+}
@@ -2,12 +2,47 @@ use cargo_test_support::project;
 use cargo_test_support::registry::Package;
 use cargo_test_support::str;
 
+mod broad_members_glob;
+mod cfg_target_has_lib_dependency_mismatch;
+mod conflicting_dependency_source;
+mod deep_path_dependency;
+mod dependency_path_outside_workspace;
+mod dependency_registry_not_configured;
+mod deprecated_manifest_key;
+mod duplicate_feature_value;
 mod error;
+mod feature_activates_default_of_optional_dep;
+mod feature_body_references_removed_dep;
+mod feature_enables_optional_dep_without_question_mark;
 mod implicit_features;
 mod inherited;
+mod manifest_byte_order_mark;
+mod manifest_inconsistent_indentation;
+mod manifest_trailing_whitespace;
+mod misplaced_lints_table;
+mod overly_broad_dependency;
+mod package_field_order;
+mod panic_setting_in_lib_profile;
+mod private_dependency_feature;
+mod redundant_default_features_true;
+mod redundant_optional_false;
+mod redundant_target_dependency;
+mod script_incompatible_key;
+mod self_referential_feature;
+mod suspicious_key_casing;
+mod tab_indentation;
+mod target_platform_triple_typo;
+mod unknown_dependency_feature;
 mod unknown_lints;
+mod unknown_profile_field;
+mod unused_feature;
 mod unused_optional_dependencies;
+mod unused_workspace_package_field;
 mod warning;
+mod wildcard_dependency;
+mod workspace_default_members_not_subset;
+mod workspace_package_unused_in_virtual_only_workspace;
+mod workspace_resolver_mismatch;
 
 #[cargo_test]
 fn dashes_dont_get_rewritten() {
@@ -88,6 +123,290 @@ test_dummy_unstable = { level = "forbid", priority = -1 }
         .run();
 }
 
+#[cargo_test]
+fn group_level_overrides_default_on_equal_priority() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+im-a-teapot = true
+
+[lints.cargo]
+test_dummy_unstable = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "test-dummy-unstable"])
+        .with_stderr_data(str![[r#"
+[WARNING] `im_a_teapot` is specified
+ --> Cargo.toml:9:1
+  |
+9 | im-a-teapot = true
+  | ------------------
+  |
+  = [NOTE] `cargo::im_a_teapot` is set to `warn` in `[lints]`
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn group_priority_higher_than_lint() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+im-a-teapot = true
+
+[lints.cargo]
+im_a_teapot = { level = "warn", priority = -1 }
+test_dummy_unstable = { level = "deny", priority = 0 }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "test-dummy-unstable"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `im_a_teapot` is specified
+ --> Cargo.toml:9:1
+  |
+9 | im-a-teapot = true
+  | ^^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::im_a_teapot` is set to `deny` in `[lints]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn lint_priority_equal_to_group() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+im-a-teapot = true
+
+[lints.cargo]
+im_a_teapot = { level = "warn", priority = 1 }
+test_dummy_unstable = { level = "deny", priority = 1 }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "test-dummy-unstable"])
+        .with_stderr_data(str![[r#"
+[WARNING] `im_a_teapot` is specified
+ --> Cargo.toml:9:1
+  |
+9 | im-a-teapot = true
+  | ------------------
+  |
+  = [NOTE] `cargo::im_a_teapot` is set to `warn` in `[lints]`
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explain_lint_levels() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+im-a-teapot = true
+
+[lints.cargo]
+im_a_teapot = { level = "warn", priority = 10 }
+test_dummy_unstable = { level = "forbid", priority = -1 }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints -Zexplain-lint-levels")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "explain-lint-levels", "test-dummy-unstable"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `im_a_teapot` is specified
+ --> Cargo.toml:9:1
+  |
+9 | im-a-teapot = true
+  | ^^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::im_a_teapot` is set to `forbid` in `[lints]`
+  = [NOTE] considered `cargo::test_dummy_unstable`: `forbid` in `[lints]` with priority -1 (selected, as `forbid` cannot be overridden)
+  = [NOTE] considered `cargo::im_a_teapot`: `warn` in `[lints]` with priority 10
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explain_lint_levels_priority() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+im-a-teapot = true
+
+[lints.cargo]
+im_a_teapot = "deny"
+test_dummy_unstable = { level = "warn", priority = 1 }
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints -Zexplain-lint-levels")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "explain-lint-levels", "test-dummy-unstable"])
+        .with_stderr_data(str![[r#"
+[WARNING] `im_a_teapot` is specified
+ --> Cargo.toml:9:1
+  |
+9 | im-a-teapot = true
+  | ------------------
+  |
+  = [NOTE] `cargo::im_a_teapot` is set to `warn` in `[lints]`
+  = [NOTE] considered `cargo::test_dummy_unstable`: `warn` in `[lints]` with priority 1 (selected, as it has the highest priority)
+  = [NOTE] considered `cargo::im_a_teapot`: `deny` in `[lints]` with priority 0
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explain_lint_levels_tie_with_group() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+im-a-teapot = true
+
+[lints.cargo]
+im_a_teapot = "warn"
+test_dummy_unstable = "deny"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints -Zexplain-lint-levels")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "explain-lint-levels", "test-dummy-unstable"])
+        .with_stderr_data(str![[r#"
+[WARNING] `im_a_teapot` is specified
+ --> Cargo.toml:9:1
+  |
+9 | im-a-teapot = true
+  | ------------------
+  |
+  = [NOTE] `cargo::im_a_teapot` is set to `warn` in `[lints]`
+  = [NOTE] considered `cargo::test_dummy_unstable`: `deny` in `[lints]` with priority 0
+  = [NOTE] considered `cargo::im_a_teapot`: `warn` in `[lints]` with priority 0 (selected, as a lint wins ties with its groups)
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explain_lint_levels_tie_with_default() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+im-a-teapot = true
+
+[lints.cargo]
+test_dummy_unstable = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints -Zexplain-lint-levels")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "explain-lint-levels", "test-dummy-unstable"])
+        .with_stderr_data(str![[r#"
+[WARNING] `im_a_teapot` is specified
+ --> Cargo.toml:9:1
+  |
+9 | im-a-teapot = true
+  | ------------------
+  |
+  = [NOTE] `cargo::im_a_teapot` is set to `warn` in `[lints]`
+  = [NOTE] considered `cargo::test_dummy_unstable`: `warn` in `[lints]` with priority 0 (selected, as it is specified in `[lints]` and the others with its priority are not)
+  = [NOTE] considered `cargo::im_a_teapot`: `allow` by default with priority 0
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn workspace_lints() {
     let p = project()
@@ -260,6 +579,124 @@ im_a_teapot = "warn"
         .run();
 }
 
+#[cargo_test]
+fn check_feature_gated_short_message_format() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+im_a_teapot = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints --message-format short")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+Cargo.toml:9:1: error: im_a_teapot: use of unstable lint `im_a_teapot`
+[ERROR] encountered 1 errors(s) while verifying lints
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn multiple_lints_reported_together() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+im_a_teapot = "warn"
+this-lint-does-not-exist = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] use of unstable lint `im_a_teapot`
+ --> Cargo.toml:9:1
+  |
+9 | im_a_teapot = "warn"
+  | ^^^^^^^^^^^ this is behind `test-dummy-unstable`, which is not enabled
+  |
+  = [HELP] consider adding `cargo-features = ["test-dummy-unstable"]` to the top of the manifest
+[WARNING] unknown lint: `this-lint-does-not-exist`
+  --> Cargo.toml:10:1
+   |
+10 | this-lint-does-not-exist = "warn"
+   | ^^^^^^^^^^^^^^^^^^^^^^^^
+   |
+   = [NOTE] `cargo::unknown_lints` is set to `warn` by default
+[ERROR] encountered 1 errors(s) while verifying lints
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn unknown_lints_reported_in_source_order_with_other_lints() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+this-lint-does-not-exist = "warn"
+im_a_teapot = "warn"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[WARNING] unknown lint: `this-lint-does-not-exist`
+ --> Cargo.toml:9:1
+  |
+9 | this-lint-does-not-exist = "warn"
+  | ^^^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::unknown_lints` is set to `warn` by default
+[ERROR] use of unstable lint `im_a_teapot`
+  --> Cargo.toml:10:1
+   |
+10 | im_a_teapot = "warn"
+   | ^^^^^^^^^^^ this is behind `test-dummy-unstable`, which is not enabled
+   |
+   = [HELP] consider adding `cargo-features = ["test-dummy-unstable"]` to the top of the manifest
+[ERROR] encountered 1 errors(s) while verifying lints
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn check_feature_gated_workspace() {
     let p = project()
@@ -325,3 +762,162 @@ workspace = true
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn workspace_warnings_allow() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["foo"]
+
+[workspace.lints.cargo]
+warnings = "allow"
+implicit_features = "deny"
+"#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[buildDependencies]
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("foo/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] implicit features for optional dependencies is deprecated and will be unavailable in the 2024 edition
+ --> foo/Cargo.toml:8:1
+  |
+8 | bar = { version = "0.1.0", optional = true }
+  | ^^^
+  |
+  = [NOTE] `cargo::implicit_features` is set to `deny` in `[lints]`
+[ERROR] encountered 1 errors(s) while running lints
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn warnings_deny() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[buildDependencies]
+
+[lints.cargo]
+warnings = "deny"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] suspicious casing of manifest key
+ --> Cargo.toml:7:2
+  |
+7 | [buildDependencies]
+  |  ^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::suspicious_key_casing` is set to `deny` in `[lints]`
+  = [HELP] did you mean `build-dependencies`?
+[ERROR] encountered 1 errors(s) while running lints
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn specific_lint_overrides_warnings() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[buildDependencies]
+
+[lints.cargo]
+warnings = "deny"
+suspicious_key_casing = "allow"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unused manifest key: buildDependencies
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn lint_and_group_forbid() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[lints.cargo]
+self_referential_feature = "forbid"
+suspicious = "forbid"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[NOTE] `cargo::self_referential_feature` is also forbidden by `cargo::suspicious`
+ --> Cargo.toml:9:1
+  |
+8 | self_referential_feature = "forbid"
+  | ------------------------ the lint's own `forbid` is the one reported
+9 | suspicious = "forbid"
+  | ---------- this also forbids `cargo::self_referential_feature`
+  |
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,97 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn broad_requirements() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "1.2.0").publish();
+    Package::new("qux", "1.2.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = "0"
+baz = { version = ">=0" }
+qux = "1.2"
+
+[dev-dependencies]
+baz = ">= 1.2"
+
+[lints.cargo]
+overly_broad_dependency = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] dependency `bar` accepts nearly every version
+ --> Cargo.toml:8:7
+  |
+8 | bar = "0"
+  |       ---
+  |
+  = [NOTE] `cargo::overly_broad_dependency` is set to `warn` in `[lints]`
+  = [HELP] specify the versions that are supported
+[WARNING] dependency `baz` accepts nearly every version
+ --> Cargo.toml:9:19
+  |
+9 | baz = { version = ">=0" }
+  |                   -----
+  |
+  = [HELP] specify the versions that are supported
+[WARNING] dependency `baz` accepts nearly every version
+  --> Cargo.toml:13:7
+   |
+13 | baz = ">= 1.2"
+   |       --------
+   |
+   = [HELP] use a caret requirement, like `"1.2"`
+[UPDATING] `dummy-registry` index
+[LOCKING] 4 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allowed_by_default() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = "0"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,59 @@
+use cargo_test_support::prelude::*;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+const MANIFEST: &str = r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2015"
+
+[profile.release]
+panic = "abort"
+
+[lints.cargo]
+panic_setting_in_lib_profile = "warn"
+"#;
+
+#[cargo_test]
+fn lib_only() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `profile.release.panic` is set, but `foo` only has a library
+ --> Cargo.toml:8:1
+  |
+8 | panic = "abort"
+  | -----
+  |
+  = [NOTE] `cargo::panic_setting_in_lib_profile` is set to `warn` in `[lints]`
+  = [NOTE] the panic strategy is chosen when linking a binary, so it is ignored for the library
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn bin() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .file("src/main.rs", "fn main() {}")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
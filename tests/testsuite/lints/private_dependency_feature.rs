@@ -0,0 +1,111 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn private_feature() {
+    Package::new("bar", "0.1.0")
+        .feature("std", &[])
+        .feature("__internal", &[])
+        .publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["bar/std", "bar?/__internal"]
+
+[lints.cargo]
+private_dependency_feature = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `foo` activates private feature `bar?/__internal`
+  --> Cargo.toml:11:19
+   |
+11 | foo = ["bar/std", "bar?/__internal"]
+   |                   -----------------
+   |
+   = [NOTE] `cargo::private_dependency_feature` is set to `warn` in `[lints]`
+   = [HELP] features starting with `_` are internal to `bar` and not meant to be enabled by dependents
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn public_feature() {
+    Package::new("bar", "0.1.0").feature("std", &[]).publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["bar/std"]
+
+[lints.cargo]
+private_dependency_feature = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![""])
+        .run();
+}
+
+#[cargo_test]
+fn skipped_offline() {
+    Package::new("bar", "0.1.0")
+        .feature("__internal", &[])
+        .publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = true }
+
+[features]
+foo = ["bar/__internal"]
+
+[lints.cargo]
+private_dependency_feature = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints --offline")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![""])
+        .run();
+}
@@ -0,0 +1,140 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+const MANIFEST: &str = r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", default-features = true }
+baz = { version = "0.1.0", default-features = false }
+
+[build-dependencies]
+bar = { version = "0.1.0", default-features = true, features = [] }
+
+[lints.cargo]
+redundant_default_features_true = "warn"
+"#;
+
+#[cargo_test]
+fn warn() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `default-features = true` is redundant
+ --> Cargo.toml:8:28
+  |
+8 | bar = { version = "0.1.0", default-features = true }
+  |                            ----------------
+  |
+  = [NOTE] `cargo::redundant_default_features_true` is set to `warn` in `[lints]`
+  = [HELP] remove `default-features`
+[WARNING] `default-features = true` is redundant
+  --> Cargo.toml:12:28
+   |
+12 | bar = { version = "0.1.0", default-features = true, features = [] }
+   |                            ----------------
+   |
+   = [HELP] remove `default-features`
+[UPDATING] `dummy-registry` index
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (2 fixes)
+...
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0" }
+baz = { version = "0.1.0", default-features = false }
+
+[build-dependencies]
+bar = { version = "0.1.0", features = [] }
+
+[lints.cargo]
+redundant_default_features_true = "warn"
+
+"#]],
+    );
+}
+
+#[cargo_test]
+fn overrides_workspace_dependency() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["foo"]
+
+[workspace.dependencies]
+bar = { version = "0.1.0", default-features = false }
+"#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { workspace = true, default-features = true }
+
+[lints.cargo]
+redundant_default_features_true = "warn"
+"#,
+        )
+        .file("foo/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.1.0 (registry `dummy-registry`)
+[CHECKING] bar v0.1.0
+[CHECKING] foo v0.1.0 ([ROOT]/foo/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
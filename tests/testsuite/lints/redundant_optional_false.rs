@@ -0,0 +1,184 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+const MANIFEST: &str = r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = false }
+baz = { version = "0.1.0", optional = true }
+
+[dev-dependencies]
+bar = { version = "0.1.0", optional = false, features = [] }
+
+[target.'cfg(all())'.build-dependencies]
+bar = { version = "0.1.0", optional = false }
+
+[features]
+baz = ["dep:baz"]
+
+[lints.cargo]
+redundant_optional_false = "warn"
+"#;
+
+#[cargo_test]
+fn warn() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `optional = false` is redundant
+ --> Cargo.toml:8:28
+  |
+8 | bar = { version = "0.1.0", optional = false }
+  |                            --------
+  |
+  = [NOTE] `cargo::redundant_optional_false` is set to `warn` in `[lints]`
+  = [HELP] remove `optional`
+[WARNING] `optional = false` is redundant
+  --> Cargo.toml:12:28
+   |
+12 | bar = { version = "0.1.0", optional = false, features = [] }
+   |                            --------
+   |
+   = [HELP] remove `optional`
+[WARNING] `optional = false` is redundant
+  --> Cargo.toml:15:28
+   |
+15 | bar = { version = "0.1.0", optional = false }
+   |                            --------
+   |
+   = [HELP] remove `optional`
+[UPDATING] `dummy-registry` index
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (3 fixes)
+...
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0" }
+baz = { version = "0.1.0", optional = true }
+
+[dev-dependencies]
+bar = { version = "0.1.0", features = [] }
+
+[target.'cfg(all())'.build-dependencies]
+bar = { version = "0.1.0" }
+
+[features]
+baz = ["dep:baz"]
+
+[lints.cargo]
+redundant_optional_false = "warn"
+
+"#]],
+    );
+}
+
+#[cargo_test]
+fn fix_edition() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["edition2024"]
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = false }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --edition --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["edition2024", "cargo-lints"])
+        .with_stderr_data(str![[r#"
+[MIGRATING] Cargo.toml from 2021 edition to 2024
+[FIXED] Cargo.toml (1 fix)
+...
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        str![[r#"
+
+cargo-features = ["edition2024"]
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0" }
+
+"#]],
+    );
+}
+
+#[cargo_test]
+fn fix_edition_requires_cargo_lints() {
+    Package::new("bar", "0.1.0").publish();
+    let manifest = r#"
+cargo-features = ["edition2024"]
+
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", optional = false }
+"#;
+    let p = project()
+        .file("Cargo.toml", manifest)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --edition --allow-no-vcs")
+        .masquerade_as_nightly_cargo(&["edition2024"])
+        .run();
+    assert_eq!(p.read_file("Cargo.toml"), manifest);
+}
@@ -0,0 +1,107 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn tautology() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[target.'cfg(all())'.dependencies]
+
+[target.'cfg(not(any()))'.dependencies]
+
+[target.'cfg(any(unix, all()))'.dependencies]
+
+[target.'cfg(unix)'.dependencies]
+
+[lints.cargo]
+redundant_target_dependency = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `cfg(all())` is always true
+ --> Cargo.toml:7:9
+  |
+7 | [target.'cfg(all())'.dependencies]
+  |         ------------
+  |
+  = [NOTE] `cargo::redundant_target_dependency` is set to `warn` in `[lints]`
+  = [HELP] move the dependencies to `[dependencies]`
+[WARNING] `cfg(not(any()))` is always true
+ --> Cargo.toml:9:9
+  |
+9 | [target.'cfg(not(any()))'.dependencies]
+  |         -----------------
+  |
+  = [HELP] move the dependencies to `[dependencies]`
+[WARNING] `cfg(any(unix, all()))` is always true
+  --> Cargo.toml:11:9
+   |
+11 | [target.'cfg(any(unix, all()))'.dependencies]
+   |         -----------------------
+   |
+   = [HELP] move the dependencies to `[dependencies]`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn contradiction() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[target.'cfg(any())'.dependencies]
+
+[target.'cfg(all(unix, not(all())))'.dependencies]
+
+[lints.cargo]
+redundant_target_dependency = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] `cfg(any())` is never true
+ --> Cargo.toml:7:9
+  |
+7 | [target.'cfg(any())'.dependencies]
+  |         ------------
+  |
+  = [NOTE] `cargo::redundant_target_dependency` is set to `warn` in `[lints]`
+  = [HELP] remove the table, its dependencies are never used
+[WARNING] `cfg(all(unix, not(all())))` is never true
+ --> Cargo.toml:9:9
+  |
+9 | [target.'cfg(all(unix, not(all())))'.dependencies]
+  |         ----------------------------
+  |
+  = [HELP] remove the table, its dependencies are never used
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,102 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn dependency_key() {
+    Package::new("bar", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", defaultFeatures = false }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] suspicious casing of manifest key
+ --> Cargo.toml:8:28
+  |
+8 | bar = { version = "0.1.0", defaultFeatures = false }
+  |                            ---------------
+  |
+  = [NOTE] `cargo::suspicious_key_casing` is set to `warn` by default
+  = [HELP] did you mean `default-features`?
+[WARNING] unused manifest key: dependencies.bar.defaultFeatures
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn top_level_key() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[buildDependencies]
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] suspicious casing of manifest key
+ --> Cargo.toml:7:2
+  |
+7 | [buildDependencies]
+  |  -----------------
+  |
+  = [NOTE] `cargo::suspicious_key_casing` is set to `warn` by default
+  = [HELP] did you mean `build-dependencies`?
+[WARNING] unused manifest key: buildDependencies
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allowed() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[buildDependencies]
+
+[lints.cargo]
+suspicious_key_casing = "allow"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unused manifest key: buildDependencies
+...
+"#]])
+        .run();
+}
@@ -0,0 +1,110 @@
+use cargo_test_support::compare::assert_e2e;
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+const MANIFEST: &str = "
+[package]
+name = \"foo\"
+version = \"0.1.0\"
+edition = \"2015\"
+authors = [
+\t\"a\",
+ \t\"b\",
+    \"c\",
+]
+description = \"\"\"
+\tfoo\"\"\"
+
+[lints.cargo]
+tab_indentation = { level = \"warn\", tab-width = 2 }
+";
+
+#[cargo_test]
+fn warn() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] manifest line is indented with tabs
+ --> Cargo.toml:7:1
+  |
+7 | [..]"a",
+  |[..]
+  |
+  = [NOTE] `cargo::tab_indentation` is set to `warn` in `[lints]`
+  = [HELP] indent with spaces instead
+[WARNING] manifest line is indented with tabs
+ --> Cargo.toml:8:1
+  |
+8 | [..]"b",
+  |[..]
+  |
+  = [HELP] indent with spaces instead
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allowed_by_default() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            &MANIFEST.replace("tab_indentation = { level = \"warn\", tab-width = 2 }", ""),
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn fix() {
+    let p = project()
+        .file("Cargo.toml", MANIFEST)
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("fix --allow-no-vcs -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[FIXED] Cargo.toml (2 fixes)
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+    // Tabs within the multi-line string are part of its value
+    assert_e2e().eq(
+        p.read_file("Cargo.toml"),
+        "
+[package]
+name = \"foo\"
+version = \"0.1.0\"
+edition = \"2015\"
+authors = [
+  \"a\",
+   \"b\",
+    \"c\",
+]
+description = \"\"\"
+\tfoo\"\"\"
+
+[lints.cargo]
+tab_indentation = { level = \"warn\", tab-width = 2 }
+",
+    );
+}
@@ -0,0 +1,67 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn typo() {
+    Package::new("bar", "0.1.0")
+        .feature("serde", &[])
+        .feature("std", &[])
+        .publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", features = ["std", "serd"] }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[WARNING] feature `serd` is not a feature of `bar`
+ --> Cargo.toml:8:47
+  |
+8 | bar = { version = "0.1.0", features = ["std", "serd"] }
+  |                                               ------
+  |
+  = [NOTE] `cargo::unknown_dependency_feature` is set to `warn` by default
+  = [HELP] there is a feature with a similar name: `serde`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn skipped_offline() {
+    Package::new("bar", "0.1.0").feature("serde", &[]).publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = { version = "0.1.0", features = ["serd"] }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("manifest-lints check -Zcargo-lints --offline")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![""])
+        .run();
+}
@@ -69,7 +69,7 @@ workspace = true
     p.cargo("check -Zcargo-lints")
         .masquerade_as_nightly_cargo(&["cargo-lints"])
         .with_stderr_data(str![[r#"
-[WARNING] unknown lint: `this-lint-does-not-exist`
+[WARNING] unknown lint: `this-lint-does-not-exist` (from workspace manifest)
  --> Cargo.toml:6:1
   |
 6 | this-lint-does-not-exist = "warn"
@@ -88,3 +88,247 @@ workspace = true
 "#]])
         .run();
 }
+
+#[cargo_test]
+fn inherited_from_outside_workspace() {
+    let foo = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[dependencies]
+bar = { path = "../bar" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+    let _bar = project()
+        .at("bar")
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+
+[workspace.lints.cargo]
+this-lint-does-not-exist = "warn"
+
+[package]
+name = "bar"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints]
+workspace = true
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    foo.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unknown lint: `this-lint-does-not-exist`
+  |
+  = [NOTE] `cargo::unknown_lints` is set to `warn` by default
+[LOCKING] 2 packages to latest compatible versions
+[CHECKING] bar v0.0.1 ([ROOT]/bar)
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn json_message_format() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+unused-optional-dependency = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints --message-format json")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stdout_data(
+            str![[r#"
+{"applicability":"MaybeIncorrect","level":"warn","manifest_path":"[ROOT]/foo/Cargo.toml","message":"unknown lint: `unused-optional-dependency`","name":"unknown_lints","reason":"cargo-manifest-lint","since":"1.78","span":{"end":113,"start":87},"suggestion":{"applicability":"MaybeIncorrect","kind":"lint","name":"unused_optional_dependency"}}
+{"executable":null,"features":[],"filenames":"{...}","fresh":false,"manifest_path":"[ROOT]/foo/Cargo.toml","package_id":"path+[ROOTURL]/foo#0.0.1","profile":"{...}","reason":"compiler-artifact","target":"{...}"}
+{"reason":"build-finished","success":true}
+
+"#]]
+            .json_lines(),
+        )
+        .run();
+}
+
+#[cargo_test]
+fn suggest_close_typo() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+unused_optional_dependecy = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unknown lint: `unused_optional_dependecy`
+ --> Cargo.toml:9:1
+  |
+9 | unused_optional_dependecy = "warn"
+  | ^^^^^^^^^^^^^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::unknown_lints` is set to `warn` by default
+  = [HELP] there is a lint with a similar name: `unused_optional_dependency`
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn no_suggestion_for_unrelated_name() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+xyzzy = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unknown lint: `xyzzy`
+ --> Cargo.toml:9:1
+  |
+9 | xyzzy = "warn"
+  | ^^^^^
+  |
+  = [NOTE] `cargo::unknown_lints` is set to `warn` by default
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn reported_in_source_order() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+zzz-not-a-lint = "warn"
+aaa-not-a-lint = "warn"
+mmm-not-a-lint = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unknown lint: `zzz-not-a-lint`
+ --> Cargo.toml:9:1
+  |
+9 | zzz-not-a-lint = "warn"
+  | ^^^^^^^^^^^^^^
+  |
+  = [NOTE] `cargo::unknown_lints` is set to `warn` by default
+[WARNING] unknown lint: `aaa-not-a-lint`
+  --> Cargo.toml:10:1
+   |
+10 | aaa-not-a-lint = "warn"
+   | ^^^^^^^^^^^^^^
+   |
+[WARNING] unknown lint: `mmm-not-a-lint`
+  --> Cargo.toml:11:1
+   |
+11 | mmm-not-a-lint = "warn"
+   | ^^^^^^^^^^^^^^
+   |
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn short_message_format() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.0.1"
+edition = "2015"
+authors = []
+
+[lints.cargo]
+this-lint-does-not-exist = "warn"
+unused-optional-dependency = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints --message-format short")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+Cargo.toml:9:1: warning: unknown_lints: unknown lint: `this-lint-does-not-exist`
+Cargo.toml:10:1: warning: unknown_lints: unknown lint: `unused-optional-dependency`
+[CHECKING] foo v0.0.1 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,120 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn unknown_field() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[profile.release]
+optlevel = 2
+lto = "tru"
+
+[profile.dev.build-override]
+debugg = 1
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unknown field `optlevel` in `profile.release`
+ --> Cargo.toml:8:1
+  |
+8 | optlevel = 2
+  | --------
+  |
+  = [NOTE] `cargo::unknown_profile_field` is set to `warn` by default
+  = [HELP] a field with a similar name exists: `opt-level`
+[WARNING] `lto` setting of `"tru"` in `profile.release` is not valid
+ --> Cargo.toml:9:7
+  |
+9 | lto = "tru"
+  |       -----
+  |
+  = [HELP] use a boolean, `"thin"`, `"fat"`, or `"off"`
+[WARNING] unknown field `debugg` in `profile.dev.build-override`
+  --> Cargo.toml:12:1
+   |
+12 | debugg = 1
+   | ------
+   |
+   = [HELP] a field with a similar name exists: `debug`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn allow() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[profile.release]
+optlevel = 2
+
+[lints.cargo]
+unknown_profile_field = "allow"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+// An invalid `panic` is rejected when the manifest is loaded, so it never
+// reaches the lint
+#[cargo_test]
+fn invalid_panic() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[profile.release]
+panic = "abrot"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at `[ROOT]/foo/Cargo.toml`
+
+Caused by:
+  `panic` setting of `abrot` is not a valid setting, must be `unwind` or `abort`
+
+"#]])
+        .run();
+}
@@ -0,0 +1,253 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn dead_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+default = ["std"]
+std = []
+unused = []
+
+[lints.cargo]
+unused_feature = "warn"
+"#,
+        )
+        .file(
+            "src/lib.rs",
+            r#"
+#[cfg(feature = "std")]
+pub fn foo() {}
+"#,
+        )
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `unused` is unused
+  --> Cargo.toml:10:1
+   |
+10 | unused = []
+   | ------
+   |
+   = [NOTE] `cargo::unused_feature` is set to `warn` in `[lints]`
+   = [HELP] remove the feature, or check for it with `#[cfg(feature = "unused")]`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn used_only_in_cfg() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+in-module = []
+in-test = []
+in-build = []
+
+[lints.cargo]
+unused_feature = "warn"
+"#,
+        )
+        .file("src/lib.rs", "mod inner;")
+        .file(
+            "src/inner.rs",
+            r#"
+#[cfg_attr(feature="in-module", allow(dead_code))]
+fn foo() {}
+"#,
+        )
+        .file(
+            "tests/it.rs",
+            r#"
+#[test]
+fn it() {
+    assert!(cfg!(feature = "in-test") || true);
+}
+"#,
+        )
+        .file(
+            "build.rs",
+            r#"
+fn main() {
+    let _ = std::env::var_os("CARGO_FEATURE_IN_BUILD");
+}
+"#,
+        )
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[COMPILING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn referenced_by_feature() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+full = ["marker"]
+marker = []
+
+[lints.cargo]
+unused_feature = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn referenced_by_required_features() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+for-bin = []
+for-example = []
+for-test = []
+for-bench = []
+
+[[bin]]
+name = "tool"
+required-features = ["for-bin"]
+
+[[example]]
+name = "ex"
+required-features = ["for-example"]
+
+[[test]]
+name = "it"
+required-features = ["for-test"]
+
+[[bench]]
+name = "bench"
+required-features = ["for-bench"]
+
+[lints.cargo]
+unused_feature = "warn"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .file("src/bin/tool.rs", "fn main() {}")
+        .file("examples/ex.rs", "fn main() {}")
+        .file("tests/it.rs", "")
+        .file("benches/bench.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn ignores_nested_packages() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[lib]
+path = "lib.rs"
+
+[features]
+nested = []
+
+[lints.cargo]
+unused_feature = "warn"
+"#,
+        )
+        .file("lib.rs", "")
+        .file(
+            "nested/Cargo.toml",
+            r#"
+[package]
+name = "nested"
+version = "0.1.0"
+edition = "2021"
+
+[features]
+nested = []
+"#,
+        )
+        .file(
+            "nested/src/lib.rs",
+            r#"
+#[cfg(feature = "nested")]
+pub fn nested() {}
+"#,
+        )
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] feature `nested` is unused
+  --> Cargo.toml:11:1
+   |
+11 | nested = []
+   | ------
+   |
+   = [NOTE] `cargo::unused_feature` is set to `warn` in `[lints]`
+   = [HELP] remove the feature, or check for it with `#[cfg(feature = "nested")]`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -2,6 +2,7 @@
 
 use cargo_test_support::project;
 use cargo_test_support::registry::Package;
+use cargo_test_support::str;
 
 #[cargo_test(nightly, reason = "edition2024 is not stable")]
 fn default() {
@@ -201,3 +202,44 @@ warning: unused optional dependency
         )
         .run();
 }
+
+#[cargo_test(nightly, reason = "edition2024 is not stable")]
+fn allowed_by_comment() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+cargo-features = ["edition2024"]
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2024"
+
+[dependencies]
+# cargo::allow(unused_optional_dependency)
+bar = { version = "0.1.0", optional = true }
+baz = { version = "0.1.0", optional = true }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints", "edition2024"])
+        .with_stderr_data(str![[r#"
+[WARNING] unused optional dependency
+  --> Cargo.toml:11:1
+   |
+11 | baz = { version = "0.1.0", optional = true }
+   | ---
+   |
+   = [NOTE] `cargo::unused_optional_dependency` is set to `warn` by default
+   = [HELP] remove the dependency or activate it in a feature with `dep:baz`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
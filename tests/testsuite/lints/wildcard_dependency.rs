@@ -0,0 +1,196 @@
+use cargo_test_support::project;
+use cargo_test_support::registry::Package;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn deny() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = "*"
+
+[dev-dependencies]
+baz = { version = "*" }
+
+[lints.cargo]
+wildcard_dependency = "deny"
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] dependency `bar` has a wildcard version requirement
+ --> Cargo.toml:8:7
+  |
+8 | bar = "*"
+  |       ^^^
+  |
+  = [NOTE] `cargo::wildcard_dependency` is set to `deny` in `[lints]`
+  = [HELP] specify the versions that are supported
+[ERROR] dependency `baz` has a wildcard version requirement
+  --> Cargo.toml:11:19
+   |
+11 | baz = { version = "*" }
+   |                   ^^^
+   |
+   = [HELP] specify the versions that are supported
+[ERROR] encountered 2 errors(s) while running lints
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn scope_normal() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = "0.1.0"
+
+[dev-dependencies]
+baz = "*"
+
+[lints.cargo]
+wildcard_dependency = { level = "deny", scope = "normal" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[UPDATING] `dummy-registry` index
+[LOCKING] 3 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v0.1.0 (registry `dummy-registry`)
+[CHECKING] bar v0.1.0
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn scope_dev() {
+    Package::new("bar", "0.1.0").publish();
+    Package::new("baz", "0.1.0").publish();
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+bar = "*"
+
+[dev-dependencies]
+baz = "*"
+
+[lints.cargo]
+wildcard_dependency = { level = "deny", scope = "dev" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] dependency `baz` has a wildcard version requirement
+  --> Cargo.toml:11:7
+   |
+11 | baz = "*"
+   |       ^^^
+   |
+   = [NOTE] `cargo::wildcard_dependency` is set to `deny` in `[lints]`
+   = [HELP] specify the versions that are supported
+[ERROR] encountered 1 errors(s) while running lints
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn invalid_scope() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[lints.cargo]
+wildcard_dependency = { level = "deny", scope = "all" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] `scope` for `cargo::wildcard_dependency` must be `normal`, `dev`, or `build`, but found "all"
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn unsupported_scope() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2021"
+
+[lints.cargo]
+implicit_features = { level = "warn", scope = "dev" }
+"#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] unused manifest key: `lints.cargo.implicit_features.scope`
+[CHECKING] foo v0.1.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
@@ -0,0 +1,87 @@
+use cargo_test_support::project;
+use cargo_test_support::str;
+
+#[cargo_test]
+fn conflicting() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["foo"]
+resolver = "1"
+"#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2015"
+resolver = "2"
+"#,
+        )
+        .file("foo/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[WARNING] resolver for the non root package will be ignored, specify resolver at the workspace root:
+package:   [ROOT]/foo/foo/Cargo.toml
+workspace: [ROOT]/foo/Cargo.toml
+[WARNING] `resolver = "2"` is ignored for workspace members
+ --> foo/Cargo.toml:6:1
+  |
+6 | resolver = "2"
+  | --------
+  |
+[NOTE] the workspace uses `resolver = "1"`
+ --> Cargo.toml:4:1
+  |
+4 | resolver = "1"
+  | --------
+  |
+  = [NOTE] `cargo::workspace_resolver_mismatch` is set to `warn` by default
+  = [HELP] remove `resolver` here, or set it in the workspace root's manifest instead
+[CHECKING] foo v0.1.0 ([ROOT]/foo/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn matching() {
+    let p = project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+members = ["foo"]
+resolver = "2"
+"#,
+        )
+        .file(
+            "foo/Cargo.toml",
+            r#"
+[package]
+name = "foo"
+version = "0.1.0"
+edition = "2015"
+resolver = "2"
+"#,
+        )
+        .file("foo/src/lib.rs", "")
+        .build();
+
+    p.cargo("check -Zcargo-lints")
+        .masquerade_as_nightly_cargo(&["cargo-lints"])
+        .with_stderr_data(str![[r#"
+[CHECKING] foo v0.1.0 ([ROOT]/foo/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
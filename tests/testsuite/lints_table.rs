@@ -268,6 +268,37 @@ Caused by:
         .run();
 }
 
+#[cargo_test]
+fn fail_on_duplicate_lint() {
+    let foo = project()
+        .file(
+            "Cargo.toml",
+            r#"
+                [package]
+                name = "foo"
+                version = "0.0.1"
+                edition = "2015"
+                authors = []
+
+                [lints.cargo]
+                im_a_teapot = "warn"
+
+                [lints.cargo.im_a_teapot]
+                level = "deny"
+            "#,
+        )
+        .file("src/lib.rs", "")
+        .build();
+
+    foo.cargo("check")
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] duplicate key `im_a_teapot`[..]
+...
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn package_lint_deny() {
     let foo = project()
@@ -42,6 +42,7 @@ mod cargo_install;
 mod cargo_locate_project;
 mod cargo_login;
 mod cargo_logout;
+mod cargo_manifest_lints;
 mod cargo_metadata;
 mod cargo_new;
 mod cargo_owner;
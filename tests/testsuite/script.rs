@@ -1,4 +1,5 @@
 use cargo_test_support::basic_manifest;
+use cargo_test_support::compare::assert_e2e;
 use cargo_test_support::prelude::*;
 use cargo_test_support::registry::Package;
 use cargo_test_support::str;
@@ -36,7 +37,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `echo.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] echo v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]`
@@ -45,25 +46,779 @@ args: []
         .run();
 }
 
+#[cargo_test]
+fn workspace_isolated_by_default() {
+    let p = cargo_test_support::project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+resolver = "2"
+"#,
+        )
+        .file("scripts/echo.rs", ECHO_SCRIPT)
+        .build();
+
+    p.cargo("-Zscript -v scripts/echo.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]
+args: []
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `scripts/echo.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] echo v0.0.0 ([ROOT]/foo/scripts)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]`
+
+"#]])
+        .run();
+    assert!(!p.root().join("Cargo.lock").exists());
+}
+
+#[cargo_test]
+fn workspace_joined() {
+    let p = cargo_test_support::project()
+        .file(
+            "Cargo.toml",
+            r#"
+[workspace]
+resolver = "2"
+"#,
+        )
+        .file(
+            ".cargo/config.toml",
+            r#"
+[script]
+join-workspace = true
+"#,
+        )
+        .file("scripts/echo.rs", ECHO_SCRIPT)
+        .build();
+
+    p.cargo("-Zscript -v scripts/echo.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/foo/target/debug/echo[EXE]
+args: []
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `scripts/echo.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] echo v0.0.0 ([ROOT]/foo/scripts)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/foo/target/debug/echo[EXE]`
+
+"#]])
+        .run();
+    assert!(p.root().join("Cargo.lock").exists());
+}
+
 #[cargo_test]
 fn basic_path() {
     let p = cargo_test_support::project()
-        .file("echo", ECHO_SCRIPT)
+        .file("echo", ECHO_SCRIPT)
+        .build();
+
+    p.cargo("-Zscript -v ./echo")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]
+args: []
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `echo`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] echo v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn basic_stdin() {
+    let p = cargo_test_support::project().build();
+
+    p.cargo("-Zscript -")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdin(
+            r#"
+fn main() {
+    println!("Hello world!");
+}
+"#,
+        )
+        .with_stdout_data(str![[r#"
+Hello world!
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `<stdin>`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] stdin-[..] v0.0.0 ([ROOT]/home/.cargo/script-stdin)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/stdin-[..][EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cmd_script_eval() {
+    let p = cargo_test_support::project().build();
+
+    p.cargo("-Zscript script --eval")
+        .arg(r#"println!("{}", 1+1)"#)
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+2
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `../home/.cargo/script-eval/[..]/cargo_eval.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] cargo_eval v0.0.0 ([ROOT]/home/.cargo/script-eval/[..])
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/cargo_eval[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cmd_script_eval_quiet() {
+    let p = cargo_test_support::project().build();
+
+    p.cargo("-Zscript -q script --eval")
+        .arg(r#"println!("{}", 1+1)"#)
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+2
+
+"#]])
+        .with_stderr_data("")
+        .run();
+}
+
+#[cargo_test]
+fn cmd_script_timings() {
+    let p = cargo_test_support::project().build();
+
+    p.cargo("-Zscript script --timings --eval")
+        .arg(r#"println!("{}", 1+1)"#)
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+2
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `../home/.cargo/script-eval/[..]/cargo_eval.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] cargo_eval v0.0.0 ([ROOT]/home/.cargo/script-eval/[..])
+      Timing report saved to [ROOT]/home/.cargo/target/[HASH]/cargo-timings/cargo-timing-[..].html
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/cargo_eval[EXE]`
+
+"#]])
+        .run();
+
+    let target = cargo_test_support::paths::home().join(".cargo/target");
+    let report = std::fs::read_dir(target)
+        .unwrap()
+        .flat_map(|prefix| std::fs::read_dir(prefix.unwrap().path()).unwrap())
+        .map(|dir| dir.unwrap().path().join("cargo-timings/cargo-timing.html"))
+        .find(|report| report.exists());
+    assert!(report.is_some(), "no timing report was written");
+}
+
+#[cargo_test]
+fn cmd_script_eval_with_config() {
+    Package::new("bar", "1.0.0")
+        .file(
+            "src/lib.rs",
+            r#"pub fn hello() -> &'static str { "hello from bar" }"#,
+        )
+        .publish();
+    let p = cargo_test_support::project().build();
+
+    p.cargo("-Zscript script --config")
+        .arg(r#"script.default-dependencies.bar = "1.0""#)
+        .arg("--eval")
+        .arg(r#"println!("{}", bar::hello())"#)
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+hello from bar
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `../home/.cargo/script-eval/[..]/cargo_eval.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v1.0.0 (registry `dummy-registry`)
+[COMPILING] bar v1.0.0
+[COMPILING] cargo_eval v0.0.0 ([ROOT]/home/.cargo/script-eval/[..])
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/cargo_eval[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cmd_script_extract() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[package]
+edition = "2021"
+---
+
+fn main() {
+    println!("Hello world!");
+}"#;
+    let p = cargo_test_support::project()
+        .file("echo.rs", script)
+        .build();
+
+    p.cargo("-Zscript script --extract pkg echo.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stderr_data(str![[r#"
+[CREATED] package at `[ROOT]/foo/pkg` from `[ROOT]/foo/echo.rs`
+
+"#]])
+        .run();
+    assert_e2e().eq(
+        p.read_file("pkg/src/main.rs"),
+        str![[r#"
+
+fn main() {
+    println!("Hello world!");
+}
+"#]],
+    );
+
+    p.cargo("build")
+        .cwd(p.root().join("pkg"))
+        .with_stderr_data(str![[r#"
+[COMPILING] echo v0.0.0 ([ROOT]/foo/pkg)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cmd_script_eval_requires_z_flag() {
+    let p = cargo_test_support::project().build();
+
+    p.cargo("script --eval")
+        .arg(r#"println!("{}", 1+1)"#)
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] the `cargo script` command is unstable, pass `-Z script` to enable it
+See https://github.com/rust-lang/cargo/issues/12207 for more information about the `cargo script` command.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn manifest_out() {
+    let p = cargo_test_support::project()
+        .file("echo.rs", ECHO_SCRIPT)
+        .build();
+
+    p.cargo("-Zscript -Zscript-manifest-out=- echo.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+[[bin]]
+name = "echo"
+path = "[ROOT]/foo/echo.rs"
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2021"
+name = "echo"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `echo.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+
+"#]])
+        .run();
+
+    p.cargo("-Zscript -Zscript-manifest-out=echo.toml echo.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data("")
+        .run();
+    let manifest = p.read_file("echo.toml");
+    assert!(manifest.contains("[workspace]"), "{manifest}");
+    assert!(manifest.contains("echo.rs"), "{manifest}");
+}
+
+#[cargo_test]
+fn print_expanded_source() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[dependencies]
+---
+
+fn main() {
+    println!("Hello world!");
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript -Zscript-print=expanded-source script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+
+fn main() {
+    println!("Hello world!");
+}
+
+"#]])
+        .with_stderr_data("")
+        .run();
+}
+
+#[cargo_test]
+fn invalid_frontmatter_toml() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[dependencies]
+time 0.1.25
+---
+
+fn main() {}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] expected `.`, `=`
+ --> script.rs:4:6
+  |
+4 | time 0.1.25
+  |      ^
+...
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn explicit_package_name() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[package]
+name = "my-script"
+---
+
+fn main() {
+    let bin = std::env::args_os().next().unwrap();
+    println!("bin: {}", bin.to_str().unwrap());
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript -v script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/home/.cargo/target/[HASH]/debug/my-script[EXE]
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] my-script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/my-script[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn env_table() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[env]
+GREETING = "Hello from env"
+---
+
+fn main() {
+    println!("{}", env!("GREETING"));
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+Hello from env
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn env_table_with_manifest_path() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[env]
+GREETING = "Hello from env"
+---
+
+fn main() {
+    println!("{}", env!("GREETING"));
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript run --manifest-path script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+Hello from env
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn env_table_stdin_with_cli_config() {
+    let p = cargo_test_support::project().build();
+
+    p.cargo("-Zscript -vv --config build.rustflags=['--cfg=from_cli'] -")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdin(
+            r#"---
+[env]
+GREETING = "Hello from env"
+---
+
+fn main() {
+    println!("{}", env!("GREETING"));
+}
+"#,
+        )
+        .with_stdout_data(str![[r#"
+Hello from env
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `<stdin>`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] stdin-[..] v0.0.0 ([ROOT]/home/.cargo/script-stdin)
+[RUNNING] `rustc --crate-name stdin_[..] -L dependency=[ROOT]/home/.cargo/target/[HASH]/debug/deps --cfg=from_cli`
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/stdin-[..][EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn default_dependencies_from_config() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[dependencies]
+clap = "4.0"
+---
+
+fn main() {}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [script.default-dependencies]
+                anyhow = "1.0"
+                clap = { version = "3.0", features = ["derive"] }
+            "#,
+        )
+        .build();
+
+    p.cargo("-Zscript -Zscript-manifest-out=- script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+[[bin]]
+name = "script"
+path = "[ROOT]/home/.cargo/target/[HASH]/script.rs"
+
+[dependencies]
+anyhow = "1.0"
+clap = "4.0"
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2021"
+name = "script"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn default_edition_from_config() {
+    let script = r#"#!/usr/bin/env cargo
+
+fn main() {}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [script]
+                default-edition = "2018"
+            "#,
+        )
+        .build();
+
+    p.cargo("-Zscript -Zscript-manifest-out=- script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+[[bin]]
+name = "script"
+path = "[ROOT]/foo/script.rs"
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2018"
+name = "script"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]])
+        .with_stderr_data("")
+        .run();
+}
+
+#[cargo_test]
+fn default_edition_from_config_overridden_by_manifest() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[package]
+edition = "2021"
+---
+
+fn main() {}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [script]
+                default-edition = "2018"
+            "#,
+        )
+        .build();
+
+    p.cargo("-Zscript -Zscript-manifest-out=- script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+...
+edition = "2021"
+...
+"#]])
+        .with_stderr_data("")
+        .run();
+}
+
+#[cargo_test]
+fn default_dependencies_opt_out() {
+    let script = r#"#!/usr/bin/env cargo
+---
+script.default-dependencies = false
+---
+
+fn main() {}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [script.default-dependencies]
+                anyhow = "1.0"
+            "#,
+        )
+        .build();
+
+    p.cargo("-Zscript -Zscript-manifest-out=- script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+[[bin]]
+name = "script"
+path = "[ROOT]/home/.cargo/target/[HASH]/script.rs"
+
+[package]
+autobenches = false
+autobins = false
+autoexamples = false
+autotests = false
+build = false
+edition = "2021"
+name = "script"
+
+[profile.release]
+strip = true
+
+[workspace]
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cargo_features() {
+    let script = r#"#!/usr/bin/env cargo
+---
+cargo-features = ["test-dummy-unstable"]
+
+[package]
+edition = "2021"
+im-a-teapot = true
+---
+
+fn main() {
+    println!("Hello world!");
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script", "test-dummy-unstable"])
+        .with_stdout_data(str![[r#"
+Hello world!
+
+"#]])
+        .with_stderr_data(str![[r#"
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn lib_only_script() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[lib]
+path = "greet.rs"
+---
+
+pub fn greet() -> &'static str {
+    "hello"
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("greet.rs", script)
         .build();
 
-    p.cargo("-Zscript -v ./echo")
+    p.cargo("-Zscript greet.rs")
         .masquerade_as_nightly_cargo(&["script"])
-        .with_stdout_data(str![[r#"
-bin: [ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]
-args: []
+        .with_status(101)
+        .with_stderr_data(str![[r#"
+[ERROR] failed to parse manifest at [ROOT]/foo/greet.rs
+...
+  `lib` is not allowed in embedded manifests
 
 "#]])
+        .run();
+}
+
+#[cargo_test]
+fn cargo_features_required() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[package]
+edition = "2021"
+im-a-teapot = true
+---
+
+fn main() {}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script", "test-dummy-unstable"])
+        .with_status(101)
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
-[COMPILING] echo v0.0.0 ([ROOT]/foo)
-[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
-[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]`
+[ERROR] failed to parse manifest at `[ROOT]/foo/script.rs`
 
+Caused by:
+  the `im-a-teapot` manifest key is unstable and may not work properly in England
+
+Caused by:
+  feature `test-dummy-unstable` is required
+...
 "#]])
         .run();
 }
@@ -135,7 +890,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `echo.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] echo v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/echo[EXE]`
@@ -168,6 +923,73 @@ For more information, see issue #12207 <https://github.com/rust-lang/cargo/issue
         .run();
 }
 
+/// `PATH` with the `cargo` under test first, so a script's shebang finds it
+#[cfg(unix)]
+fn path_with_cargo() -> std::ffi::OsString {
+    let mut path = path();
+    path.insert(
+        0,
+        cargo_test_support::cargo_exe()
+            .parent()
+            .unwrap()
+            .to_path_buf(),
+    );
+    std::env::join_paths(path.iter()).unwrap()
+}
+
+#[cargo_test]
+#[cfg(unix)]
+fn executable_with_plain_shebang() {
+    let script = r#"#!/usr/bin/env cargo
+
+fn main() {
+    println!("Hello world!");
+}"#;
+    let p = cargo_test_support::project()
+        .executable("script.rs", script)
+        .build();
+
+    p.process(p.root().join("script.rs"))
+        .env("PATH", path_with_cargo())
+        .env("CARGO_UNSTABLE_SCRIPT", "true")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+Hello world!
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+#[cfg(unix)]
+fn executable_with_split_string_shebang() {
+    let script = r#"#!/usr/bin/env -S cargo -Zscript --quiet
+
+fn main() {
+    println!("Hello world!");
+}"#;
+    let p = cargo_test_support::project()
+        .executable("script.rs", script)
+        .build();
+
+    p.process(p.root().join("script.rs"))
+        .env("PATH", path_with_cargo())
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+Hello world!
+
+"#]])
+        .with_stderr_data("")
+        .run();
+}
+
 #[cargo_test]
 fn requires_nightly() {
     let p = cargo_test_support::project()
@@ -252,7 +1074,7 @@ Hello world!
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
@@ -280,7 +1102,7 @@ msg = undefined
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
@@ -296,7 +1118,7 @@ msg = undefined
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
 
@@ -312,7 +1134,7 @@ msg = hello
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
@@ -419,7 +1241,7 @@ line: 4
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
@@ -443,7 +1265,7 @@ args: ["-NotAnArg"]
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] -NotAnArg`
@@ -467,7 +1289,7 @@ args: ["-NotAnArg"]
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] -NotAnArg`
@@ -476,6 +1298,39 @@ args: ["-NotAnArg"]
         .run();
 }
 
+#[cargo_test]
+fn test_separated_args() {
+    let script = ECHO_SCRIPT;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript -v script.rs -- --flag value")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]
+args: ["--flag", "value"]
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] --flag value`
+
+"#]])
+        .run();
+
+    p.cargo("-Zscript -v script.rs -- --")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]
+args: ["--"]
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn test_same_flags() {
     let script = ECHO_SCRIPT;
@@ -491,7 +1346,7 @@ args: ["--help"]
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] --help`
@@ -515,7 +1370,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `s-h.w§c!.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] s-h-w-c- v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/s-h-w-c-[EXE]`
@@ -539,7 +1394,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `42answer.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] answer v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/answer[EXE]`
@@ -561,7 +1416,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `42.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] package v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/package[EXE]`
@@ -707,7 +1562,7 @@ Hello world!
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [UPDATING] `dummy-registry` index
 [LOCKING] 2 packages to latest compatible versions
 [DOWNLOADING] crates ...
@@ -721,6 +1576,139 @@ Hello world!
         .run();
 }
 
+#[cargo_test]
+fn frozen_without_lockfile() {
+    Package::new("bar", "1.0.0").publish();
+    let script = r#"#!/usr/bin/env cargo
+---
+[dependencies]
+bar = "1.0.0"
+---
+
+fn main() {
+    println!("Hello world!");
+}"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("--frozen -Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_status(101)
+        .with_stdout_data("")
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[ERROR] the lock file [ROOT]/home/.cargo/target/[HASH]/Cargo.lock needs to be updated but --frozen was passed to prevent this
+If you want to try to generate the lock file without accessing the network, remove the --frozen flag and use --offline instead.
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn frozen_with_lockfile() {
+    Package::new("bar", "1.0.0").publish();
+    let script = r#"#!/usr/bin/env cargo
+---
+[dependencies]
+bar = "1.0.0"
+---
+
+fn main() {
+    println!("Hello world!");
+}"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    // A newer version must not be picked up while frozen
+    Package::new("bar", "1.0.1").publish();
+
+    p.cargo("--frozen -Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+Hello world!
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
+
+"#]])
+        .run();
+}
+
+/// Runs a script whose embedded manifest has `dependencies` as its
+/// `[dependencies]` table, checks that the lockfile written for it is up to
+/// date with `--locked`, and returns that lockfile
+fn script_lockfile(dependencies: &str) -> String {
+    let script = format!(
+        r#"#!/usr/bin/env cargo
+---
+[package]
+edition = "2021"
+
+[dependencies]
+{dependencies}
+---
+
+fn main() {{}}
+"#
+    );
+    let p = cargo_test_support::project()
+        .file("script.rs", &script)
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+    p.cargo("--locked -Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    let target = cargo_test_support::paths::home().join(".cargo/target");
+    let lockfile = std::fs::read_dir(target)
+        .unwrap()
+        .flat_map(|prefix| std::fs::read_dir(prefix.unwrap().path()).unwrap())
+        .map(|dir| dir.unwrap().path().join("Cargo.lock"))
+        .find(|lockfile| lockfile.exists())
+        .expect("no lockfile was written for the script");
+    std::fs::read_to_string(lockfile).unwrap()
+}
+
+#[cargo_test]
+fn lockfile_with_registry_dep() {
+    Package::new("baz", "1.0.0").publish();
+
+    assert_e2e().eq(
+        script_lockfile(r#"baz = "1.0.0""#),
+        str![[r##"
+# This file is automatically @generated by Cargo.
+# It is not intended for manual editing.
+version = 3
+
+[[package]]
+name = "baz"
+version = "1.0.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+checksum = "[..]"
+
+[[package]]
+name = "script"
+version = "0.0.0"
+dependencies = [
+ "baz",
+]
+
+"##]],
+    );
+}
+
 #[cargo_test]
 fn test_path_dep() {
     let script = r#"#!/usr/bin/env cargo
@@ -734,24 +1722,115 @@ fn main() {
 }"#;
     let p = cargo_test_support::project()
         .file("script.rs", script)
-        .file("src/lib.rs", "pub fn foo() {}")
-        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.1"))
+        .file("src/lib.rs", "pub fn foo() {}")
+        .file("bar/Cargo.toml", &basic_manifest("bar", "0.0.1"))
+        .file("bar/src/lib.rs", "pub fn bar() {}")
+        .build();
+
+    p.cargo("-Zscript -v script.rs --help")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+Hello world!
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[LOCKING] 2 packages to latest compatible versions
+[COMPILING] bar v0.0.1 ([ROOT]/foo/bar)
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] --help`
+
+"#]])
+        .run();
+}
+
+#[cargo_test(nightly, reason = "edition2024 is not stable")]
+fn test_dep_with_newer_edition() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[dependencies]
+bar.path = "./bar"
+---
+
+fn main() {
+    println!("Hello world!");
+}"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .file(
+            "bar/Cargo.toml",
+            r#"
+                cargo-features = ["edition2024"]
+
+                [package]
+                name = "bar"
+                version = "0.0.1"
+                edition = "2024"
+            "#,
+        )
+        .file("bar/src/lib.rs", "pub fn bar() {}")
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script", "edition2024"])
+        .with_stdout_data(str![[r#"
+Hello world!
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[LOCKING] 2 packages to latest compatible versions
+[WARNING] dependency `bar v0.0.1 ([ROOT]/foo/bar)` uses edition 2024, which is newer than edition 2021 used by `script`; consider setting `package.edition = "2024"`
+[COMPILING] bar v0.0.1 ([ROOT]/foo/bar)
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn test_dep_with_newer_edition_than_explicit_edition() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[package]
+edition = "2018"
+
+[dependencies]
+bar.path = "./bar"
+---
+
+fn main() {
+    println!("Hello world!");
+}"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .file(
+            "bar/Cargo.toml",
+            r#"
+                [package]
+                name = "bar"
+                version = "0.0.1"
+                edition = "2021"
+            "#,
+        )
         .file("bar/src/lib.rs", "pub fn bar() {}")
         .build();
 
-    p.cargo("-Zscript -v script.rs --help")
+    p.cargo("-Zscript script.rs")
         .masquerade_as_nightly_cargo(&["script"])
         .with_stdout_data(str![[r#"
 Hello world!
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
 [LOCKING] 2 packages to latest compatible versions
 [COMPILING] bar v0.0.1 ([ROOT]/foo/bar)
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
-[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] --help`
+[RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
 
 "#]])
         .run();
@@ -776,7 +1855,7 @@ Hello world!
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] --help`
@@ -804,7 +1883,7 @@ Hello world!
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE] --help`
@@ -828,7 +1907,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
@@ -837,6 +1916,209 @@ args: []
         .run();
 }
 
+#[cargo_test]
+fn env_target_dir() {
+    let script = ECHO_SCRIPT;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript -v script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .env("CARGO_TARGET_DIR", "custom-target")
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/foo/custom-target/debug/script[EXE]
+args: []
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `[ROOT]/foo/custom-target/debug/script[EXE]`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cli_target_dir() {
+    let script = ECHO_SCRIPT;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript run --manifest-path script.rs --target-dir custom-target")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+bin: [ROOT]/foo/custom-target/debug/script[EXE]
+args: []
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] `custom-target/debug/script[EXE]`
+
+"#]])
+        .run();
+    assert!(p.root().join("custom-target/debug").is_dir());
+}
+
+/// Creates a fake script target directory taking up `size` bytes, last used
+/// `days_ago`
+fn script_cache_entry(name: &str, size: usize, days_ago: u64) -> std::path::PathBuf {
+    let dir = cargo_test_support::paths::home()
+        .join(".cargo/target")
+        .join(&name[..2])
+        .join(&name[2..]);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(".cargo-script"), "").unwrap();
+    std::fs::write(dir.join("data"), vec![0; size]).unwrap();
+    let used =
+        std::time::SystemTime::now() - std::time::Duration::from_secs(days_ago * 24 * 60 * 60);
+    let used = filetime::FileTime::from_system_time(used);
+    filetime::set_file_mtime(&dir, used).unwrap();
+    dir
+}
+
+#[cargo_test]
+fn script_cache_max_age() {
+    let p = cargo_test_support::project()
+        .file("script.rs", ECHO_SCRIPT)
+        .file(
+            ".cargo/config.toml",
+            r#"
+[script.cache]
+max-age = "7 days"
+"#,
+        )
+        .build();
+    let old = script_cache_entry("aaold", 10, 30);
+    let recent = script_cache_entry("aarecent", 10, 1);
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    assert!(!old.exists());
+    assert!(recent.exists());
+}
+
+#[cargo_test]
+fn script_cache_max_size() {
+    let p = cargo_test_support::project()
+        .file("script.rs", ECHO_SCRIPT)
+        .file(
+            ".cargo/config.toml",
+            r#"
+[script.cache]
+max-size = "1500"
+"#,
+        )
+        .build();
+    let old = script_cache_entry("aaold", 1000, 30);
+    let recent = script_cache_entry("bbrecent", 1000, 1);
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    assert!(!old.exists());
+    assert!(!old.parent().unwrap().exists());
+    assert!(recent.exists());
+}
+
+#[cargo_test]
+fn script_cache_removes_stdin_scripts() {
+    let p = cargo_test_support::project()
+        .file("script.rs", ECHO_SCRIPT)
+        .file(
+            ".cargo/config.toml",
+            r#"
+[script.cache]
+max-age = "7 days"
+"#,
+        )
+        .build();
+    let old = script_cache_entry("aaold", 10, 30);
+    let stdin_dir = cargo_test_support::paths::home().join(".cargo/script-stdin");
+    std::fs::create_dir_all(&stdin_dir).unwrap();
+    let old_script = stdin_dir.join("stdin-old.rs");
+    std::fs::write(&old_script, ECHO_SCRIPT).unwrap();
+    std::fs::write(old.join(".cargo-script"), old_script.to_str().unwrap()).unwrap();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    assert!(!old.exists());
+    assert!(!old_script.exists());
+}
+
+#[cargo_test]
+fn script_cache_skips_unrelated_directories() {
+    let p = cargo_test_support::project()
+        .file("script.rs", ECHO_SCRIPT)
+        .file(
+            ".cargo/config.toml",
+            r#"
+[script.cache]
+max-age = "7 days"
+"#,
+        )
+        .build();
+    let old = script_cache_entry("aaold", 10, 30);
+    std::fs::remove_file(old.join(".cargo-script")).unwrap();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    assert!(old.exists());
+}
+
+#[cargo_test]
+fn script_cache_pruned_recently() {
+    let p = cargo_test_support::project()
+        .file("script.rs", ECHO_SCRIPT)
+        .file(
+            ".cargo/config.toml",
+            r#"
+[script.cache]
+max-age = "7 days"
+"#,
+        )
+        .build();
+    let old = script_cache_entry("aaold", 10, 30);
+    std::fs::write(
+        cargo_test_support::paths::home().join(".cargo/target/.cargo-script-last-pruned"),
+        "",
+    )
+    .unwrap();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    assert!(old.exists());
+}
+
+#[cargo_test]
+fn script_cache_unconfigured() {
+    let p = cargo_test_support::project()
+        .file("script.rs", ECHO_SCRIPT)
+        .build();
+    let old = script_cache_entry("aaold", 1000, 365);
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .run();
+
+    assert!(old.exists());
+}
+
 #[cargo_test]
 fn no_local_lockfile() {
     let script = ECHO_SCRIPT;
@@ -855,7 +2137,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
@@ -912,7 +2194,7 @@ fn cmd_check_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_stdout_data("")
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [CHECKING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 
@@ -920,6 +2202,114 @@ fn cmd_check_with_embedded() {
         .run();
 }
 
+#[cargo_test]
+fn cmd_check_with_embedded_type_error() {
+    let script = r#"#!/usr/bin/env cargo
+
+fn main() {
+    println!("main ran");
+    let _: u32 = "not a number";
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript check --manifest-path script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_status(101)
+        .with_stdout_data("")
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[CHECKING] script v0.0.0 ([ROOT]/foo)
+error[E0308]: mismatched types
+...
+[ERROR] could not compile `script` (bin "script") due to 1 previous error
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn undeclared_crate_help() {
+    let script = r#"#!/usr/bin/env cargo
+---
+[dependencies]
+---
+
+use regex::Regex;
+
+fn main() {
+    let _ = Regex::new("");
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_status(101)
+        .with_stdout_data("")
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+...
+[ERROR] could not compile `script` (bin "script") due to 1 previous error
+[NOTE] if the script uses these crates, add them to its embedded `[dependencies]`: `regex`
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn undeclared_crate_help_skips_default_dependencies() {
+    Package::new("bar", "1.0.0")
+        .file(
+            "src/lib.rs",
+            r#"pub fn hello() -> String { "hello from bar".into() }"#,
+        )
+        .publish();
+    let script = r#"#!/usr/bin/env cargo
+
+use bar::hello;
+use regex::Regex;
+
+fn main() {
+    let _ = Regex::new(&hello());
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .file(
+            ".cargo/config.toml",
+            r#"
+                [script.default-dependencies]
+                bar = "1.0"
+            "#,
+        )
+        .build();
+
+    p.cargo("-Zscript script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_status(101)
+        .with_stdout_data("")
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] bar v1.0.0 (registry `dummy-registry`)
+[COMPILING] bar v1.0.0
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+...
+[ERROR] could not compile `script` (bin "script") due to 1 previous error
+[NOTE] if the script uses these crates, add them to its embedded `[dependencies]`: `regex`
+
+"#]])
+        .run();
+}
+
 #[cargo_test]
 fn cmd_check_with_missing_script_rs() {
     let p = cargo_test_support::project().build();
@@ -961,7 +2351,7 @@ fn cmd_build_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_stdout_data("")
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 
@@ -988,7 +2378,55 @@ test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; fini
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[COMPILING] script v0.0.0 ([ROOT]/foo)
+[FINISHED] `test` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
+[RUNNING] unittests script.rs ([ROOT]/home/.cargo/target/[HASH]/debug/deps/script-[HASH][EXE])
+
+"#]])
+        .run();
+}
+
+#[cargo_test]
+fn cmd_test_with_embedded_dev_dependency() {
+    Package::new("helper", "1.0.0")
+        .file("src/lib.rs", "pub fn answer() -> u32 { 42 }")
+        .publish();
+    let script = r#"#!/usr/bin/env cargo
+---
+[dev-dependencies]
+helper = "1.0.0"
+---
+
+fn main() {}
+
+#[test]
+fn uses_dev_dep() {
+    assert_eq!(helper::answer(), 42);
+}
+"#;
+    let p = cargo_test_support::project()
+        .file("script.rs", script)
+        .build();
+
+    p.cargo("-Zscript test --manifest-path script.rs")
+        .masquerade_as_nightly_cargo(&["script"])
+        .with_stdout_data(str![[r#"
+
+running 1 test
+test uses_dev_dep ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in [ELAPSED]s
+
+
+"#]])
+        .with_stderr_data(str![[r#"
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
+[UPDATING] `dummy-registry` index
+[LOCKING] 2 packages to latest compatible versions
+[DOWNLOADING] crates ...
+[DOWNLOADED] helper v1.0.0 (registry `dummy-registry`)
+[COMPILING] helper v1.0.0
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `test` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] unittests script.rs ([ROOT]/home/.cargo/target/[HASH]/debug/deps/script-[HASH][EXE])
@@ -1013,7 +2451,7 @@ fn cmd_clean_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_stdout_data("")
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [REMOVED] [FILE_NUM] files, [FILE_SIZE]B total
 
 "#]])
@@ -1031,7 +2469,7 @@ fn cmd_generate_lockfile_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_stdout_data("")
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 
 "#]])
         .run();
@@ -1118,7 +2556,7 @@ fn cmd_metadata_with_embedded() {
             .json(),
         )
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 
 "#]])
         .run();
@@ -1180,7 +2618,7 @@ fn cmd_read_manifest_with_embedded() {
             .json(),
         )
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 
 "#]])
         .run();
@@ -1200,7 +2638,7 @@ args: []
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [COMPILING] script v0.0.0 ([ROOT]/foo)
 [FINISHED] `dev` profile [unoptimized + debuginfo] target(s) in [ELAPSED]s
 [RUNNING] `[ROOT]/home/.cargo/target/[HASH]/debug/script[EXE]`
@@ -1222,7 +2660,7 @@ script v0.0.0 ([ROOT]/foo)
 
 "#]])
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 
 "#]])
         .run();
@@ -1238,7 +2676,7 @@ fn cmd_update_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_stdout_data("")
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 
 "#]])
         .run();
@@ -1261,7 +2699,7 @@ fn cmd_verify_project_with_embedded() {
             .json(),
         )
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 
 "#]])
         .run();
@@ -1277,7 +2715,7 @@ fn cmd_pkgid_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_status(101)
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [ERROR] [ROOT]/foo/script.rs is unsupported by `cargo pkgid`
 
 "#]])
@@ -1294,7 +2732,7 @@ fn cmd_package_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_status(101)
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [ERROR] [ROOT]/foo/script.rs is unsupported by `cargo package`
 
 "#]])
@@ -1311,7 +2749,7 @@ fn cmd_publish_with_embedded() {
         .masquerade_as_nightly_cargo(&["script"])
         .with_status(101)
         .with_stderr_data(str![[r#"
-[WARNING] `package.edition` is unspecified, defaulting to `2021`
+[WARNING] `package.edition` is unspecified in `script.rs`, defaulting to `2021`; set `package.edition` in the embedded manifest to silence this warning
 [ERROR] [ROOT]/foo/script.rs is unsupported by `cargo publish`
 
 "#]])